@@ -0,0 +1,141 @@
+//! BLS12-381 signature and hash to curve tests
+//!
+//! The test data comes in three formats, so this module has a submodule
+//! for each: `verify` for single signature verification, `aggregate_verify`
+//! for verification of aggregate signatures, and `hash_to_g2` for hashing to
+//! the G2 group.
+
+use super::*;
+
+/// BLS ciphersuites
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, serde_derive::Deserialize)]
+pub enum BlsCiphersuite {
+    /// The basic scheme, with public keys in G1 and signatures in G2
+    #[serde(rename = "BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_")]
+    G2Basic,
+    /// The proof of possession scheme, with public keys in G1 and signatures in G2
+    #[serde(rename = "BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_")]
+    G2ProofOfPossession,
+}
+
+impl BlsCiphersuite {
+    /// Return the ciphersuite ID as used in the Wycheproof data
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::G2Basic => "BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_",
+            Self::G2ProofOfPossession => "BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_",
+        }
+    }
+
+    /// Return the domain separation tag used when hashing messages to sign
+    ///
+    /// For these ciphersuites this is the ciphersuite ID itself.
+    pub fn dst(&self) -> &'static [u8] {
+        self.name().as_bytes()
+    }
+}
+
+/// BLS signature verification tests
+pub mod verify {
+    use super::*;
+
+    define_test_set!("BLS verify", "bls_sig_verify_schema.json");
+
+    define_test_set_names!(
+        G2Basic => "bls_sig_g2_basic_verify",
+        G2ProofOfPossession => "bls_sig_g2_pop_verify",
+    );
+
+    define_algorithm_map!("BLS" => Bls);
+
+    define_test_flags!(
+        EmptyMessage,
+        FieldBoundary,
+        IdentityPoint,
+        InvalidEncoding,
+        InvalidFlags,
+        InvalidSignature,
+        LargeMessage,
+        MinimalInput,
+        NotInSubgroup,
+        NotOnCurve,
+        SignatureMalleability,
+        TruncatedSignature,
+        Valid,
+        WrongDST,
+        WrongKey,
+        WrongMessage,
+    );
+
+    define_test_group_type_id!(
+        "BlsSigVerify" => BlsSigVerify,
+    );
+
+    define_test_group!(
+        ciphersuite: BlsCiphersuite,
+        "publicKey" => key: BlsPublic,
+    );
+
+    define_test!(msg: ByteString, sig: ByteString);
+}
+
+/// BLS aggregate signature verification tests
+pub mod aggregate_verify {
+    use super::*;
+
+    define_test_set!("BLS aggregate verify", "bls_aggregate_verify_schema.json");
+
+    define_test_set_names!(
+        G2Basic => "bls_sig_g2_aggregate_verify",
+    );
+
+    define_algorithm_map!("BLS" => Bls);
+
+    define_test_flags!(
+        EmptyAggregate,
+        IdentityPoint,
+        InvalidFlags,
+        MismatchedCount,
+        NotInSubgroup,
+        NotOnCurve,
+        TruncatedSignature,
+        ValidAggregate,
+        WrongKey,
+        WrongMessage,
+    );
+
+    define_test_group_type_id!(
+        "BlsAggregateVerify" => BlsAggregateVerify,
+    );
+
+    define_test_group!(ciphersuite: BlsCiphersuite);
+
+    define_test!(
+        pubkeys: Vec<ByteString>,
+        messages: Vec<ByteString>,
+        sig: ByteString,
+    );
+}
+
+/// BLS12-381 hash to G2 tests
+pub mod hash_to_g2 {
+    use super::*;
+
+    define_test_set!("BLS hash to G2", "bls_hash_to_g2_schema.json");
+
+    define_test_set_names!(
+        HashToG2 => "bls_hash_to_g2",
+    );
+
+    define_algorithm_map!("BLS" => Bls);
+
+    define_test_flags!(HashToG2);
+
+    define_test_group_type_id!(
+        "BlsHashToG2" => BlsHashToG2,
+    );
+
+    define_test_group!(dst: String);
+
+    define_test!(msg: ByteString, "expected" => point: ByteString);
+}
@@ -0,0 +1,34 @@
+//! Deterministic AEAD tests
+//!
+//! These are tests of SIV mode used without a nonce, so that encryption is
+//! deterministic. The ciphertext is the synthetic IV followed by the
+//! encrypted message. The set includes the deterministic example of
+//! RFC 5297 appendix A.1; the example of appendix A.2 uses several
+//! associated data components and so cannot be represented here.
+
+use super::*;
+
+define_test_set!("DAEAD", "daead_test_schema_v1.json");
+
+define_test_set_names!(
+    AesSivCmac => "aes_siv_cmac",
+);
+
+define_algorithm_map!("AES-SIV-CMAC" => AesSivCmac);
+
+define_test_flags!(EdgeCaseSiv);
+
+define_test_group_type_id!(
+    "DaeadTest" => Daead,
+);
+
+define_test_group!(
+    "keySize" => key_size: usize,
+);
+
+define_test!(
+    key: ByteString,
+    aad: ByteString,
+    "msg" => pt: ByteString,
+    ct: ByteString,
+);
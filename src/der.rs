@@ -0,0 +1,464 @@
+//! Minimal DER support
+//!
+//! This is just enough ASN.1 DER handling to decode and encode the
+//! structures which appear in the test data, such as DER encoded signatures.
+//! Decoding is strict, and reports exactly why an encoding is not valid DER.
+//!
+//! The `SubjectPublicKeyInfo` and `PrivateKeyInfo` types split the key
+//! encodings in the test data into their algorithm and key bytes, which can
+//! then be compared against the decoded keys given alongside them.
+
+/// Errors which can occur when decoding DER
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum DerError {
+    /// The input ended before the end of an element
+    Truncated,
+    /// An element had a different tag than was expected
+    UnexpectedTag { expected: u8, actual: u8 },
+    /// An indefinite length was used, which is only allowed in BER
+    IndefiniteLength,
+    /// A length was not encoded in the shortest possible form
+    NonMinimalLength,
+    /// A length was too large to be represented
+    LengthOverflow,
+    /// An INTEGER had no content bytes
+    EmptyInteger,
+    /// An INTEGER was encoded with unnecessary leading bytes
+    NonMinimalInteger,
+    /// An INTEGER was negative where a positive value is required
+    NegativeInteger,
+    /// There was additional data following the end of the structure
+    TrailingData,
+    /// An OBJECT IDENTIFIER was empty or not minimally encoded
+    InvalidOid,
+    /// A BIT STRING was empty or had unused bits where none are allowed
+    InvalidBitString,
+    /// A key structure had a version which is not supported
+    UnsupportedVersion,
+}
+
+impl std::fmt::Display for DerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Truncated => write!(f, "DER input is truncated"),
+            Self::UnexpectedTag { expected, actual } => write!(
+                f,
+                "Unexpected DER tag {:02x} (expected {:02x})",
+                actual, expected
+            ),
+            Self::IndefiniteLength => write!(f, "DER indefinite length"),
+            Self::NonMinimalLength => write!(f, "DER length not minimally encoded"),
+            Self::LengthOverflow => write!(f, "DER length too large"),
+            Self::EmptyInteger => write!(f, "DER INTEGER is empty"),
+            Self::NonMinimalInteger => write!(f, "DER INTEGER not minimally encoded"),
+            Self::NegativeInteger => write!(f, "DER INTEGER is negative"),
+            Self::TrailingData => write!(f, "Trailing data after DER structure"),
+            Self::InvalidOid => write!(f, "Invalid DER OBJECT IDENTIFIER"),
+            Self::InvalidBitString => write!(f, "Invalid DER BIT STRING"),
+            Self::UnsupportedVersion => write!(f, "Unsupported key structure version"),
+        }
+    }
+}
+
+impl std::error::Error for DerError {}
+
+pub(crate) const TAG_INTEGER: u8 = 0x02;
+pub(crate) const TAG_BIT_STRING: u8 = 0x03;
+pub(crate) const TAG_OCTET_STRING: u8 = 0x04;
+pub(crate) const TAG_NULL: u8 = 0x05;
+pub(crate) const TAG_OID: u8 = 0x06;
+pub(crate) const TAG_SEQUENCE: u8 = 0x30;
+
+const TAG_ATTRIBUTES: u8 = 0xa0;
+const TAG_PUBLIC_KEY: u8 = 0x81;
+
+/// The object identifier of elliptic curve public keys, in dotted decimal form
+pub const EC_PUBLIC_KEY_OID: &str = "1.2.840.10045.2.1";
+
+/// The object identifier of RSA keys, in dotted decimal form
+pub const RSA_ENCRYPTION_OID: &str = "1.2.840.113549.1.1.1";
+
+/// The object identifier of RSASSA-PSS keys, in dotted decimal form
+pub const RSASSA_PSS_OID: &str = "1.2.840.113549.1.1.10";
+
+/// The object identifier of PBKDF2 (RFC 8018), in dotted decimal form
+pub const PBKDF2_OID: &str = "1.2.840.113549.1.5.12";
+
+/// The object identifier of ML-DSA-44 (FIPS 204), in dotted decimal form
+pub const ML_DSA_44_OID: &str = "2.16.840.1.101.3.4.3.17";
+
+/// The object identifier of ML-DSA-65 (FIPS 204), in dotted decimal form
+pub const ML_DSA_65_OID: &str = "2.16.840.1.101.3.4.3.18";
+
+/// The object identifier of ML-DSA-87 (FIPS 204), in dotted decimal form
+pub const ML_DSA_87_OID: &str = "2.16.840.1.101.3.4.3.19";
+
+/// A reader over a sequence of DER elements
+pub(crate) struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], DerError> {
+        if self.data.len() < len {
+            return Err(DerError::Truncated);
+        }
+        let (head, tail) = self.data.split_at(len);
+        self.data = tail;
+        Ok(head)
+    }
+
+    fn read_length(&mut self) -> Result<usize, DerError> {
+        let first = self.take(1)?[0];
+
+        if first < 0x80 {
+            return Ok(first as usize);
+        }
+        if first == 0x80 {
+            return Err(DerError::IndefiniteLength);
+        }
+
+        let bytes = self.take((first & 0x7f) as usize)?;
+        if bytes[0] == 0 {
+            return Err(DerError::NonMinimalLength);
+        }
+        if bytes.len() > std::mem::size_of::<usize>() {
+            return Err(DerError::LengthOverflow);
+        }
+
+        let len = bytes.iter().fold(0usize, |acc, &b| (acc << 8) | b as usize);
+        if len < 0x80 {
+            return Err(DerError::NonMinimalLength);
+        }
+        Ok(len)
+    }
+
+    /// Read any element, returning its tag and contents
+    pub(crate) fn read_any(&mut self) -> Result<(u8, &'a [u8]), DerError> {
+        let tag = self.take(1)?[0];
+        let len = self.read_length()?;
+        Ok((tag, self.take(len)?))
+    }
+
+    /// Read any element, returning its complete encoding
+    pub(crate) fn read_raw(&mut self) -> Result<&'a [u8], DerError> {
+        let start = self.data;
+        self.read_any()?;
+        Ok(&start[..start.len() - self.data.len()])
+    }
+
+    /// Read an element with the expected tag, returning its contents
+    pub(crate) fn read(&mut self, expected: u8) -> Result<&'a [u8], DerError> {
+        let (actual, contents) = self.read_any()?;
+        if actual != expected {
+            return Err(DerError::UnexpectedTag { expected, actual });
+        }
+        Ok(contents)
+    }
+
+    /// Read a non-negative INTEGER, returning its minimal big-endian magnitude
+    pub(crate) fn read_unsigned_integer(&mut self) -> Result<&'a [u8], DerError> {
+        let contents = self.read(TAG_INTEGER)?;
+
+        match contents {
+            [] => Err(DerError::EmptyInteger),
+            [0x00, next, ..] if *next < 0x80 => Err(DerError::NonMinimalInteger),
+            [0xff, next, ..] if *next >= 0x80 => Err(DerError::NonMinimalInteger),
+            [first, ..] if *first >= 0x80 => Err(DerError::NegativeInteger),
+            [0x00] => Ok(&[]),
+            [0x00, rest @ ..] => Ok(rest),
+            _ => Ok(contents),
+        }
+    }
+
+    /// Read an OBJECT IDENTIFIER, returning it in dotted decimal form
+    pub(crate) fn read_oid(&mut self) -> Result<String, DerError> {
+        decode_oid(self.read(TAG_OID)?)
+    }
+
+    /// Return the tag of the next element without reading it
+    pub(crate) fn peek_tag(&self) -> Option<u8> {
+        self.data.first().copied()
+    }
+
+    /// Return true if all of the input was consumed
+    pub(crate) fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Return the input which has not yet been read
+    #[cfg(feature = "rsa_sig")]
+    pub(crate) fn rest(self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Check that all of the input was consumed
+    pub(crate) fn finish(self) -> Result<(), DerError> {
+        if self.data.is_empty() {
+            Ok(())
+        } else {
+            Err(DerError::TrailingData)
+        }
+    }
+}
+
+/// An X.509 `AlgorithmIdentifier`
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct AlgorithmIdentifier<'a> {
+    /// The algorithm, in dotted decimal form
+    pub oid: String,
+    /// The complete DER encoding of the parameters, if present
+    pub parameters: Option<&'a [u8]>,
+}
+
+impl<'a> AlgorithmIdentifier<'a> {
+    pub(crate) fn read(reader: &mut Reader<'a>) -> Result<Self, DerError> {
+        let mut seq = Reader::new(reader.read(TAG_SEQUENCE)?);
+        let oid = seq.read_oid()?;
+        let parameters = if seq.is_empty() {
+            None
+        } else {
+            Some(seq.read_raw()?)
+        };
+        seq.finish()?;
+        Ok(Self { oid, parameters })
+    }
+
+    /// Return true if the parameters are an explicit NULL
+    pub fn has_null_parameters(&self) -> bool {
+        self.parameters == Some(&[TAG_NULL, 0])
+    }
+
+    /// Return the parameters decoded as an OBJECT IDENTIFIER, if they are one
+    pub fn parameters_oid(&self) -> Option<String> {
+        let mut reader = Reader::new(self.parameters?);
+        let oid = reader.read_oid().ok()?;
+        reader.finish().ok()?;
+        Some(oid)
+    }
+}
+
+/// An X.509 `SubjectPublicKeyInfo`
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct SubjectPublicKeyInfo<'a> {
+    /// The algorithm of the key
+    pub algorithm: AlgorithmIdentifier<'a>,
+    /// The contents of the `subjectPublicKey` BIT STRING
+    pub public_key: &'a [u8],
+}
+
+impl<'a> SubjectPublicKeyInfo<'a> {
+    /// Decode a DER encoded `SubjectPublicKeyInfo`
+    pub fn from_der(bytes: &'a [u8]) -> Result<Self, DerError> {
+        let mut outer = Reader::new(bytes);
+        let mut seq = Reader::new(outer.read(TAG_SEQUENCE)?);
+        outer.finish()?;
+
+        let algorithm = AlgorithmIdentifier::read(&mut seq)?;
+        let public_key = read_bit_string(&mut seq)?;
+        seq.finish()?;
+        Ok(Self {
+            algorithm,
+            public_key,
+        })
+    }
+
+    /// Return the curve of an elliptic curve key with a named curve
+    pub fn ec_curve(&self) -> Option<crate::EllipticCurve> {
+        if self.algorithm.oid != EC_PUBLIC_KEY_OID {
+            return None;
+        }
+        crate::EllipticCurve::from_oid(&self.algorithm.parameters_oid()?)
+    }
+}
+
+/// A PKCS #8 `PrivateKeyInfo`, or a `OneAsymmetricKey` from RFC 5958
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct PrivateKeyInfo<'a> {
+    /// The version, 0 for `PrivateKeyInfo` or 1 if a public key may follow
+    pub version: u8,
+    /// The algorithm of the key
+    pub algorithm: AlgorithmIdentifier<'a>,
+    /// The contents of the `privateKey` OCTET STRING
+    pub private_key: &'a [u8],
+    /// The contents of the optional `publicKey` BIT STRING
+    pub public_key: Option<&'a [u8]>,
+}
+
+impl<'a> PrivateKeyInfo<'a> {
+    /// Decode a DER encoded `PrivateKeyInfo`
+    ///
+    /// Any attributes are skipped.
+    pub fn from_der(bytes: &'a [u8]) -> Result<Self, DerError> {
+        let mut outer = Reader::new(bytes);
+        let mut seq = Reader::new(outer.read(TAG_SEQUENCE)?);
+        outer.finish()?;
+
+        let version = match seq.read_unsigned_integer()? {
+            [] => 0,
+            [1] => 1,
+            _ => return Err(DerError::UnsupportedVersion),
+        };
+        let algorithm = AlgorithmIdentifier::read(&mut seq)?;
+        let private_key = seq.read(TAG_OCTET_STRING)?;
+
+        if seq.peek_tag() == Some(TAG_ATTRIBUTES) {
+            seq.read(TAG_ATTRIBUTES)?;
+        }
+        let public_key = if version == 1 && seq.peek_tag() == Some(TAG_PUBLIC_KEY) {
+            Some(bit_string_contents(seq.read(TAG_PUBLIC_KEY)?)?)
+        } else {
+            None
+        };
+        seq.finish()?;
+
+        Ok(Self {
+            version,
+            algorithm,
+            private_key,
+            public_key,
+        })
+    }
+}
+
+// Return the contents of a BIT STRING which has no unused bits
+fn bit_string_contents(contents: &[u8]) -> Result<&[u8], DerError> {
+    match contents {
+        [0, rest @ ..] => Ok(rest),
+        _ => Err(DerError::InvalidBitString),
+    }
+}
+
+fn read_bit_string<'a>(reader: &mut Reader<'a>) -> Result<&'a [u8], DerError> {
+    bit_string_contents(reader.read(TAG_BIT_STRING)?)
+}
+
+/// Encode an element with the given tag and contents
+#[cfg(any(
+    feature = "dsa",
+    feature = "ecdsa",
+    feature = "pbes2",
+    feature = "rsa_enc",
+    feature = "rsa_sig"
+))]
+pub(crate) fn encode(tag: u8, contents: &[u8]) -> Vec<u8> {
+    let len = contents.len();
+    let mut out = vec![tag];
+
+    if len < 0x80 {
+        out.push(len as u8);
+    } else {
+        let len_bytes: Vec<u8> = len
+            .to_be_bytes()
+            .iter()
+            .copied()
+            .skip_while(|&b| b == 0)
+            .collect();
+        out.push(0x80 | len_bytes.len() as u8);
+        out.extend_from_slice(&len_bytes);
+    }
+
+    out.extend_from_slice(contents);
+    out
+}
+
+/// Encode a non-negative INTEGER from its big-endian magnitude
+#[cfg(any(
+    feature = "dsa",
+    feature = "ecdsa",
+    feature = "pbes2",
+    feature = "rsa_enc",
+    feature = "rsa_sig"
+))]
+pub(crate) fn encode_unsigned_integer(value: &[u8]) -> Vec<u8> {
+    let skip = value.iter().take_while(|&&b| b == 0).count();
+    let value = &value[skip..];
+
+    let mut contents = Vec::with_capacity(value.len() + 1);
+    if value.first().map_or(true, |&b| b >= 0x80) {
+        contents.push(0x00);
+    }
+    contents.extend_from_slice(value);
+    encode(TAG_INTEGER, &contents)
+}
+
+/// Encode a SEQUENCE of already encoded elements
+#[cfg(any(
+    feature = "dsa",
+    feature = "ecdsa",
+    feature = "pbes2",
+    feature = "rsa_enc",
+    feature = "rsa_sig"
+))]
+pub(crate) fn encode_sequence(elements: &[&[u8]]) -> Vec<u8> {
+    encode(TAG_SEQUENCE, &elements.concat())
+}
+
+/// Encode an OBJECT IDENTIFIER given in dotted decimal form
+#[cfg(any(feature = "pbes2", feature = "rsa_enc"))]
+pub(crate) fn encode_oid(oid: &str) -> Vec<u8> {
+    let arcs: Vec<u64> = oid
+        .split('.')
+        .map(|a| a.parse().expect("Valid OID"))
+        .collect();
+
+    let mut contents = Vec::new();
+    let mut encode_arc = |mut arc: u64| {
+        let mut bytes = vec![(arc & 0x7f) as u8];
+        arc >>= 7;
+        while arc > 0 {
+            bytes.push(0x80 | (arc & 0x7f) as u8);
+            arc >>= 7;
+        }
+        contents.extend(bytes.iter().rev());
+    };
+
+    encode_arc(arcs[0] * 40 + arcs[1]);
+    for &arc in &arcs[2..] {
+        encode_arc(arc);
+    }
+    encode(TAG_OID, &contents)
+}
+
+/// Decode the contents of an OBJECT IDENTIFIER into dotted decimal form
+pub(crate) fn decode_oid(contents: &[u8]) -> Result<String, DerError> {
+    if contents.is_empty() || contents[contents.len() - 1] & 0x80 != 0 {
+        return Err(DerError::InvalidOid);
+    }
+
+    let mut arcs: Vec<u64> = Vec::new();
+    let mut arc: u64 = 0;
+    let mut arc_start = true;
+
+    for &b in contents {
+        if arc_start && b == 0x80 {
+            return Err(DerError::InvalidOid);
+        }
+        if arc > (u64::MAX >> 7) {
+            return Err(DerError::InvalidOid);
+        }
+        arc = (arc << 7) | (b & 0x7f) as u64;
+        arc_start = b & 0x80 == 0;
+        if arc_start {
+            arcs.push(arc);
+            arc = 0;
+        }
+    }
+
+    let first = arcs[0];
+    let (a0, a1) = match first {
+        0..=39 => (0, first),
+        40..=79 => (1, first - 40),
+        _ => (2, first - 80),
+    };
+
+    let mut out = format!("{}.{}", a0, a1);
+    for arc in &arcs[1..] {
+        out.push_str(&format!(".{}", arc));
+    }
+    Ok(out)
+}
@@ -1,6 +1,7 @@
 //! Elliptic Curve Information
 
 use super::*;
+use std::collections::HashMap;
 
 define_test_set!("EC Curve", "ec_curve_test_schema.json");
 
@@ -32,3 +33,62 @@ define_test!(
     gy: LargeInteger,
     h: usize,
 );
+
+/// Domain parameters of a prime order elliptic curve
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct CurveParams {
+    pub name: String,
+    pub oid: String,
+    pub p: LargeInteger,
+    pub a: LargeInteger,
+    pub b: LargeInteger,
+    pub gx: LargeInteger,
+    pub gy: LargeInteger,
+    pub n: LargeInteger,
+    pub h: usize,
+}
+
+impl From<Test> for CurveParams {
+    fn from(test: Test) -> Self {
+        Self {
+            name: test.name,
+            oid: test.oid,
+            p: test.p,
+            a: test.a,
+            b: test.b,
+            gx: test.gx,
+            gy: test.gy,
+            n: test.n,
+            h: test.h,
+        }
+    }
+}
+
+impl EllipticCurve {
+    /// Return the domain parameters of this curve
+    ///
+    /// The parameters are taken from the `EcCurveInfo` data set, which is
+    /// loaded the first time this is called.
+    pub fn params(&self) -> &'static CurveParams {
+        static PARAMS: std::sync::OnceLock<HashMap<EllipticCurve, CurveParams>> =
+            std::sync::OnceLock::new();
+
+        let params = PARAMS.get_or_init(|| {
+            let test_set = TestSet::load(TestName::EcCurveInfo).expect("EC curve data is valid");
+
+            test_set
+                .test_groups
+                .into_iter()
+                .flat_map(|g| g.tests)
+                .filter_map(|t| {
+                    let curve = EllipticCurve::all()
+                        .into_iter()
+                        .find(|c| c.name() == t.name)?;
+                    Some((curve, CurveParams::from(t)))
+                })
+                .collect()
+        });
+
+        params.get(self).expect("EC curve data covers all curves")
+    }
+}
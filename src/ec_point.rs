@@ -0,0 +1,482 @@
+//! Elliptic curve point encoding
+//!
+//! Helpers for converting between affine coordinates and the SEC1 point
+//! encodings used by the ECDSA and ECDH test data.
+
+use super::*;
+
+/// The SEC1 point encoding formats
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum PointEncoding {
+    /// x coordinate only, prefixed by 0x02 or 0x03 depending on the parity of y
+    Compressed,
+    /// Both coordinates, prefixed by 0x04
+    Uncompressed,
+    /// Both coordinates, prefixed by 0x06 or 0x07 depending on the parity of y
+    Hybrid,
+}
+
+/// Errors which can occur when encoding or decoding a point
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum PointError {
+    /// The encoding was empty
+    Empty,
+    /// The first byte is not a valid SEC1 point tag
+    InvalidTag(u8),
+    /// The length of the encoding does not match what the tag requires
+    InvalidLength { expected: usize, actual: usize },
+    /// The tag of a hybrid encoding disagrees with the parity of y
+    HybridParityMismatch,
+    /// A coordinate is too large to be encoded at the field width of the curve
+    CoordinateTooLarge,
+    /// The point is the point at infinity
+    Identity,
+    /// A coordinate is not reduced modulo the field prime
+    CoordinateOutOfRange,
+    /// The point does not satisfy the curve equation
+    NotOnCurve,
+    /// The point is not in the prime order subgroup
+    NotInSubgroup,
+    /// There is no point on the curve with the x coordinate of a compressed point
+    InvalidCompressedPoint,
+    /// The key is for a different curve
+    WrongCurve,
+    /// The key is in an encoding which cannot be decoded to a point
+    UnsupportedEncoding,
+    /// The DER or PEM encoding of the key is invalid
+    InvalidAsn,
+    /// The curve is not a prime order curve
+    UnsupportedCurve,
+}
+
+impl std::fmt::Display for PointError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "Point encoding is empty"),
+            Self::InvalidTag(t) => write!(f, "Invalid point encoding tag {:02x}", t),
+            Self::InvalidLength { expected, actual } => write!(
+                f,
+                "Invalid point encoding length {} (expected {})",
+                actual, expected
+            ),
+            Self::HybridParityMismatch => write!(f, "Hybrid point tag does not match y parity"),
+            Self::CoordinateTooLarge => write!(f, "Point coordinate exceeds field width"),
+            Self::Identity => write!(f, "Point is the identity"),
+            Self::CoordinateOutOfRange => write!(f, "Point coordinate is not a field element"),
+            Self::NotOnCurve => write!(f, "Point is not on the curve"),
+            Self::NotInSubgroup => write!(f, "Point is not in the prime order subgroup"),
+            Self::InvalidCompressedPoint => write!(f, "Compressed point has no valid y coordinate"),
+            Self::WrongCurve => write!(f, "Key is for a different curve"),
+            Self::UnsupportedEncoding => write!(f, "Key encoding is not supported"),
+            Self::InvalidAsn => write!(f, "Invalid DER or PEM key encoding"),
+            Self::UnsupportedCurve => write!(f, "Curve is not a prime order curve"),
+        }
+    }
+}
+
+impl std::error::Error for PointError {}
+
+fn field_len(curve: EllipticCurve) -> usize {
+    (curve.field_bits() + 7) / 8
+}
+
+/// An elliptic curve point
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub enum EcPoint {
+    /// The point at infinity
+    Identity,
+    /// A point given by its affine coordinates
+    Affine { x: LargeInteger, y: LargeInteger },
+}
+
+impl EcPoint {
+    /// Return true if this is the point at infinity
+    pub fn is_identity(&self) -> bool {
+        matches!(self, Self::Identity)
+    }
+
+    /// Create a point on `curve` from its affine coordinates
+    ///
+    /// The coordinates are normalized to the fixed field width of the curve,
+    /// so that points compare equal regardless of how the integers were
+    /// originally encoded.
+    pub fn new(
+        curve: EllipticCurve,
+        x: &LargeInteger,
+        y: &LargeInteger,
+    ) -> Result<Self, PointError> {
+        let len = field_len(curve);
+        let x = x
+            .to_be_bytes_padded(len)
+            .ok_or(PointError::CoordinateTooLarge)?;
+        let y = y
+            .to_be_bytes_padded(len)
+            .ok_or(PointError::CoordinateTooLarge)?;
+        Ok(Self::Affine {
+            x: LargeInteger::new(x),
+            y: LargeInteger::new(y),
+        })
+    }
+
+    /// Return the SEC1 encoding of this point
+    ///
+    /// The coordinates are encoded at the fixed field width of `curve`. The
+    /// point at infinity is encoded as a single zero byte regardless of the
+    /// requested format.
+    pub fn to_sec1(
+        &self,
+        curve: EllipticCurve,
+        encoding: PointEncoding,
+    ) -> Result<Vec<u8>, PointError> {
+        let (x, y) = match self {
+            Self::Identity => return Ok(vec![0x00]),
+            Self::Affine { x, y } => (x, y),
+        };
+
+        let len = field_len(curve);
+        let x = x
+            .to_be_bytes_padded(len)
+            .ok_or(PointError::CoordinateTooLarge)?;
+        let y = y
+            .to_be_bytes_padded(len)
+            .ok_or(PointError::CoordinateTooLarge)?;
+        let y_is_odd = y[len - 1] & 1 == 1;
+
+        let mut out = Vec::with_capacity(1 + 2 * len);
+        match encoding {
+            PointEncoding::Compressed => {
+                out.push(if y_is_odd { 0x03 } else { 0x02 });
+                out.extend_from_slice(&x);
+            }
+            PointEncoding::Uncompressed => {
+                out.push(0x04);
+                out.extend_from_slice(&x);
+                out.extend_from_slice(&y);
+            }
+            PointEncoding::Hybrid => {
+                out.push(if y_is_odd { 0x07 } else { 0x06 });
+                out.extend_from_slice(&x);
+                out.extend_from_slice(&y);
+            }
+        }
+        Ok(out)
+    }
+}
+
+/// A decoded SEC1 point encoding
+///
+/// Coordinates are given at the fixed field width of the curve. Recovering
+/// y from a compressed point requires arithmetic over the field, so it is
+/// left as the x coordinate plus the parity of y.
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub enum Sec1Point {
+    /// The point at infinity
+    Identity,
+    /// A compressed point
+    Compressed { x: LargeInteger, y_is_odd: bool },
+    /// An uncompressed point
+    Uncompressed { x: LargeInteger, y: LargeInteger },
+    /// A hybrid encoded point
+    Hybrid { x: LargeInteger, y: LargeInteger },
+}
+
+impl Sec1Point {
+    /// Decode a SEC1 encoded point on `curve`
+    ///
+    /// This only checks the structure of the encoding; it does not check
+    /// that the coordinates are in range or that the point is on the curve.
+    pub fn decode(curve: EllipticCurve, bytes: &[u8]) -> Result<Self, PointError> {
+        let len = field_len(curve);
+        let tag = *bytes.first().ok_or(PointError::Empty)?;
+
+        let expected = match tag {
+            0x00 => 1,
+            0x02 | 0x03 => 1 + len,
+            0x04 | 0x06 | 0x07 => 1 + 2 * len,
+            t => return Err(PointError::InvalidTag(t)),
+        };
+
+        if bytes.len() != expected {
+            return Err(PointError::InvalidLength {
+                expected,
+                actual: bytes.len(),
+            });
+        }
+
+        let x = || LargeInteger::new(bytes[1..1 + len].to_vec());
+        let y = || LargeInteger::new(bytes[1 + len..].to_vec());
+
+        match tag {
+            0x00 => Ok(Self::Identity),
+            0x02 | 0x03 => Ok(Self::Compressed {
+                x: x(),
+                y_is_odd: tag == 0x03,
+            }),
+            0x04 => Ok(Self::Uncompressed { x: x(), y: y() }),
+            _ => {
+                let y_is_odd = bytes[bytes.len() - 1] & 1 == 1;
+                if y_is_odd != (tag == 0x07) {
+                    return Err(PointError::HybridParityMismatch);
+                }
+                Ok(Self::Hybrid { x: x(), y: y() })
+            }
+        }
+    }
+
+    /// Return the format of the encoding, or `None` for the point at infinity
+    pub fn encoding(&self) -> Option<PointEncoding> {
+        match self {
+            Self::Identity => None,
+            Self::Compressed { .. } => Some(PointEncoding::Compressed),
+            Self::Uncompressed { .. } => Some(PointEncoding::Uncompressed),
+            Self::Hybrid { .. } => Some(PointEncoding::Hybrid),
+        }
+    }
+
+    /// Return the decoded point, or `None` if the point was compressed
+    pub fn point(&self) -> Option<EcPoint> {
+        match self {
+            Self::Identity => Some(EcPoint::Identity),
+            Self::Compressed { .. } => None,
+            Self::Uncompressed { x, y } | Self::Hybrid { x, y } => Some(EcPoint::Affine {
+                x: x.clone(),
+                y: y.clone(),
+            }),
+        }
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+mod arith {
+    use num_bigint::BigUint;
+
+    pub(crate) type Point = Option<(BigUint, BigUint)>;
+
+    /// Reference (non constant time) arithmetic on a short Weierstrass curve
+    pub(crate) struct Curve {
+        pub(crate) p: BigUint,
+        pub(crate) a: BigUint,
+        pub(crate) b: BigUint,
+        pub(crate) n: BigUint,
+        pub(crate) h: BigUint,
+    }
+
+    impl Curve {
+        pub(crate) fn new(curve: super::EllipticCurve) -> Self {
+            let params = curve.params();
+            Self {
+                p: params.p.as_num_bigint(),
+                a: params.a.as_num_bigint(),
+                b: params.b.as_num_bigint(),
+                n: params.n.as_num_bigint(),
+                h: BigUint::from(params.h),
+            }
+        }
+
+        fn sub(&self, x: &BigUint, y: &BigUint) -> BigUint {
+            (x + &self.p - y) % &self.p
+        }
+
+        fn inv(&self, x: &BigUint) -> BigUint {
+            x.modpow(&(&self.p - 2u32), &self.p)
+        }
+
+        /// Return x^3 + ax + b
+        pub(crate) fn rhs(&self, x: &BigUint) -> BigUint {
+            (x * x * x + &self.a * x + &self.b) % &self.p
+        }
+
+        pub(crate) fn is_on_curve(&self, x: &BigUint, y: &BigUint) -> bool {
+            (y * y) % &self.p == self.rhs(x)
+        }
+
+        /// Return a square root of v modulo p, using Tonelli-Shanks
+        pub(crate) fn sqrt(&self, v: &BigUint) -> Option<BigUint> {
+            let zero = BigUint::from(0u32);
+            let one = BigUint::from(1u32);
+            let p = &self.p;
+            let v = v % p;
+
+            if v == zero {
+                return Some(zero);
+            }
+
+            let p1 = p - 1u32;
+            let legendre = |x: &BigUint| x.modpow(&(&p1 >> 1), p);
+
+            if legendre(&v) != one {
+                return None;
+            }
+
+            let s = p1.trailing_zeros().expect("p > 1");
+            let q = &p1 >> s;
+
+            let mut z = BigUint::from(2u32);
+            while legendre(&z) == one {
+                z += 1u32;
+            }
+
+            let mut m = s;
+            let mut c = z.modpow(&q, p);
+            let mut t = v.modpow(&q, p);
+            let mut r = v.modpow(&((&q + 1u32) >> 1), p);
+
+            while t != one {
+                let mut i = 0;
+                let mut t2 = t.clone();
+                while t2 != one {
+                    t2 = (&t2 * &t2) % p;
+                    i += 1;
+                }
+                let b = c.modpow(&(BigUint::from(1u32) << (m - i - 1)), p);
+                m = i;
+                c = (&b * &b) % p;
+                t = (t * &c) % p;
+                r = (r * b) % p;
+            }
+
+            Some(r)
+        }
+
+        pub(crate) fn add(&self, p1: &Point, p2: &Point) -> Point {
+            let (x1, y1) = match p1 {
+                None => return p2.clone(),
+                Some(pt) => pt,
+            };
+            let (x2, y2) = match p2 {
+                None => return p1.clone(),
+                Some(pt) => pt,
+            };
+
+            let lambda = if x1 == x2 {
+                if (y1 + y2) % &self.p == BigUint::from(0u32) {
+                    return None;
+                }
+                let num = (BigUint::from(3u32) * x1 * x1 + &self.a) % &self.p;
+                num * self.inv(&((y1 << 1) % &self.p)) % &self.p
+            } else {
+                self.sub(y2, y1) * self.inv(&self.sub(x2, x1)) % &self.p
+            };
+
+            let x3 = self.sub(&self.sub(&(&lambda * &lambda % &self.p), x1), x2);
+            let y3 = self.sub(&(lambda * self.sub(x1, &x3) % &self.p), y1);
+            Some((x3, y3))
+        }
+
+        pub(crate) fn mul(&self, k: &BigUint, pt: &Point) -> Point {
+            let mut r = None;
+            for i in (0..k.bits()).rev() {
+                r = self.add(&r, &r);
+                if (k >> i) & BigUint::from(1u32) == BigUint::from(1u32) {
+                    r = self.add(&r, pt);
+                }
+            }
+            r
+        }
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+impl EcPoint {
+    /// Return true if the point satisfies the curve equation
+    ///
+    /// The point at infinity is considered to be on the curve. Coordinates
+    /// which are not reduced modulo p are not on the curve.
+    pub fn is_on_curve(&self, curve: EllipticCurve) -> bool {
+        let (x, y) = match self {
+            Self::Identity => return true,
+            Self::Affine { x, y } => (x.as_num_bigint(), y.as_num_bigint()),
+        };
+        let c = arith::Curve::new(curve);
+        x < c.p && y < c.p && c.is_on_curve(&x, &y)
+    }
+
+    /// Check that this point is a valid public key on `curve`
+    ///
+    /// This performs the full public key validation of SEC1 section 3.2.2.1:
+    /// the point must not be the identity, the coordinates must be field
+    /// elements, the point must be on the curve, and multiplying the point
+    /// by the group order must give the identity.
+    pub fn validate(&self, curve: EllipticCurve) -> Result<(), PointError> {
+        let (x, y) = match self {
+            Self::Identity => return Err(PointError::Identity),
+            Self::Affine { x, y } => (x.as_num_bigint(), y.as_num_bigint()),
+        };
+        let c = arith::Curve::new(curve);
+
+        if x >= c.p || y >= c.p {
+            return Err(PointError::CoordinateOutOfRange);
+        }
+        if !c.is_on_curve(&x, &y) {
+            return Err(PointError::NotOnCurve);
+        }
+        // For prime order curves every point on the curve is in the subgroup
+        if c.h != num_bigint::BigUint::from(1u32) && c.mul(&c.n, &Some((x, y))).is_some() {
+            return Err(PointError::NotInSubgroup);
+        }
+        Ok(())
+    }
+
+    /// Return the point multiplied by the scalar `k`
+    ///
+    /// This is a simple reference implementation which is neither fast
+    /// nor constant time.
+    pub fn mul(&self, curve: EllipticCurve, k: &[u8]) -> Result<EcPoint, PointError> {
+        let pt = match self {
+            Self::Identity => None,
+            Self::Affine { x, y } => Some((x.as_num_bigint(), y.as_num_bigint())),
+        };
+        let c = arith::Curve::new(curve);
+        let k = num_bigint::BigUint::from_bytes_be(k);
+
+        match c.mul(&k, &pt) {
+            None => Ok(Self::Identity),
+            Some((x, y)) => Self::new(
+                curve,
+                &LargeInteger::new(x.to_bytes_be()),
+                &LargeInteger::new(y.to_bytes_be()),
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+impl Sec1Point {
+    /// Return the decoded point, decompressing it if required
+    pub fn to_point(&self, curve: EllipticCurve) -> Result<EcPoint, PointError> {
+        let (x, y_is_odd) = match self {
+            Self::Compressed { x, y_is_odd } => (x, *y_is_odd),
+            _ => return Ok(self.point().expect("Point is not compressed")),
+        };
+
+        let c = arith::Curve::new(curve);
+        let xi = x.as_num_bigint();
+        if xi >= c.p {
+            return Err(PointError::CoordinateOutOfRange);
+        }
+
+        let mut y = c
+            .sqrt(&c.rhs(&xi))
+            .ok_or(PointError::InvalidCompressedPoint)?;
+        if (&y % 2u32 == num_bigint::BigUint::from(1u32)) != y_is_odd {
+            y = (&c.p - y) % &c.p;
+        }
+
+        EcPoint::new(curve, x, &LargeInteger::new(y.to_bytes_be()))
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+impl EllipticCurve {
+    /// Return the generator of the curve
+    pub fn generator(&self) -> EcPoint {
+        let params = self.params();
+        EcPoint::new(*self, &params.gx, &params.gy).expect("Generator is valid")
+    }
+
+    /// Return true if `k` is a valid private scalar, that is `0 < k < n`
+    pub fn is_valid_scalar(&self, k: &[u8]) -> bool {
+        let k = num_bigint::BigUint::from_bytes_be(k);
+        k != num_bigint::BigUint::from(0u32) && k < self.params().n.as_num_bigint()
+    }
+}
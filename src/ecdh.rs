@@ -5,7 +5,9 @@ use super::*;
 define_test_set!(
     "ECDH",
     "ecdh_test_schema_v1.json",
-    "ecdh_ecpoint_test_schema_v1.json"
+    "ecdh_ecpoint_test_schema_v1.json",
+    "ecdh_webcrypto_test_schema_v1.json",
+    "ecdh_pem_test_schema_v1.json"
 );
 
 define_algorithm_map!("ECDH" => Ecdh);
@@ -21,14 +23,29 @@ define_test_set_names!(
     EcdhSecp256r1 => "ecdh_secp256r1",
     EcdhSecp384r1 => "ecdh_secp384r1",
     EcdhSecp521r1 => "ecdh_secp521r1",
+    EcdhSect283k1 => "ecdh_sect283k1",
+    EcdhSect283r1 => "ecdh_sect283r1",
+    EcdhSect409k1 => "ecdh_sect409k1",
+    EcdhSect409r1 => "ecdh_sect409r1",
+    EcdhSect571k1 => "ecdh_sect571k1",
+    EcdhSect571r1 => "ecdh_sect571r1",
     EcdhSecp224r1Ecpoint => "ecdh_secp224r1_ecpoint",
     EcdhSecp256r1Ecpoint => "ecdh_secp256r1_ecpoint",
     EcdhSecp384r1Ecpoint => "ecdh_secp384r1_ecpoint",
     EcdhSecp521r1Ecpoint => "ecdh_secp521r1_ecpoint",
+    EcdhSecp256k1Webcrypto => "ecdh_secp256k1_webcrypto",
+    EcdhSecp256r1Webcrypto => "ecdh_secp256r1_webcrypto",
+    EcdhSecp384r1Webcrypto => "ecdh_secp384r1_webcrypto",
+    EcdhSecp521r1Webcrypto => "ecdh_secp521r1_webcrypto",
+    EcdhSecp224r1Pem => "ecdh_secp224r1_pem",
+    EcdhSecp256r1Pem => "ecdh_secp256r1_pem",
+    EcdhSecp384r1Pem => "ecdh_secp384r1_pem",
+    EcdhSecp521r1Pem => "ecdh_secp521r1_pem",
 );
 
 define_test_flags!(
     AdditionChain,
+    AddSubChain,
     "CVE-2017-8932" => GolangScalarmulBug,
     "CVE_2017_10176" => JavaAdditionChainBug,
     CompressedPoint,
@@ -45,6 +62,7 @@ define_test_flags!(
     IsomorphicPublicKey,
     GroupIsomorphism,
     LargeCofactor,
+    LowOrderPublic,
     ModifiedCurveParameter,
     ModifiedCofactor,
     ModifiedGenerator,
@@ -67,17 +85,183 @@ pub enum EcdhEncoding {
     Asn1,
     #[serde(rename = "ecpoint")]
     EcPoint,
+    #[serde(rename = "webcrypto")]
+    Webcrypto,
+    #[serde(rename = "pem")]
+    Pem,
 }
 
 define_test_group_type_id!(
     "EcdhTest" => Ecdh,
     "EcdhEcpointTest" => EcdhEcpoint,
+    "EcdhWebcryptoTest" => EcdhWebcrypto,
+    "EcdhPemTest" => EcdhPem,
 );
 
-define_test_group!(curve: EllipticCurve, encoding: EcdhEncoding);
+/// The curve of an ECDH test group
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, serde_derive::Deserialize)]
+#[serde(untagged)]
+pub enum EcdhCurve {
+    /// A curve over a prime field
+    Prime(EllipticCurve),
+    /// A curve over a binary field
+    Binary(BinaryCurve),
+}
+
+impl EcdhCurve {
+    /// Return the prime field curve, or `None` for a binary curve
+    pub fn prime(&self) -> Option<EllipticCurve> {
+        match self {
+            Self::Prime(c) => Some(*c),
+            Self::Binary(_) => None,
+        }
+    }
+
+    /// Return the binary field curve, or `None` for a prime curve
+    pub fn binary(&self) -> Option<BinaryCurve> {
+        match self {
+            Self::Prime(_) => None,
+            Self::Binary(c) => Some(*c),
+        }
+    }
+
+    /// Return the name of the curve as used in the Wycheproof data
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Prime(c) => c.name(),
+            Self::Binary(c) => c.name(),
+        }
+    }
+
+    /// Return the object identifier of the curve, in dotted decimal form
+    pub fn oid(&self) -> &'static str {
+        match self {
+            Self::Prime(c) => c.oid(),
+            Self::Binary(c) => c.oid(),
+        }
+    }
+}
+
+define_test_group!(curve: EcdhCurve, encoding: EcdhEncoding);
+
+/// A public or private key, in the encoding given by the test group
+#[derive(Debug, Clone, Hash, Eq, PartialEq, serde_derive::Deserialize)]
+#[serde(from = "EncodedKey<EcdhJwk>")]
+pub enum EcdhKey {
+    /// A DER encoded key (`EcdhEncoding::Asn1`), a SEC1 point
+    /// (`EcdhEncoding::EcPoint`), or for private keys in either of these
+    /// encodings the big-endian scalar
+    Bytes(ByteString),
+    /// A JSON Web Key (`EcdhEncoding::Webcrypto`)
+    Jwk(Box<EcdhJwk>),
+    /// A PEM encoded key (`EcdhEncoding::Pem`)
+    Pem(Pem),
+}
+
+impl From<EncodedKey<EcdhJwk>> for EcdhKey {
+    fn from(key: EncodedKey<EcdhJwk>) -> Self {
+        match key {
+            EncodedKey::Bytes(b) => Self::Bytes(b),
+            EncodedKey::Jwk(jwk) => Self::Jwk(Box::new(jwk)),
+            EncodedKey::Pem(pem) => Self::Pem(pem),
+        }
+    }
+}
+
+impl EcdhKey {
+    /// Return the key bytes, or `None` if the key is a JWK or PEM
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Self::Bytes(b) => Some(b),
+            _ => None,
+        }
+    }
+
+    /// Return the JWK, or `None` if the key is in another encoding
+    pub fn as_jwk(&self) -> Option<&EcdhJwk> {
+        match self {
+            Self::Jwk(jwk) => Some(jwk),
+            _ => None,
+        }
+    }
+
+    /// Return the PEM encoding, or `None` if the key is in another encoding
+    pub fn as_pem(&self) -> Option<&Pem> {
+        match self {
+            Self::Pem(pem) => Some(pem),
+            _ => None,
+        }
+    }
+
+    /// Return the private scalar of a private key
+    ///
+    /// This is the key bytes for the `asn` and `ecpoint` encodings and the
+    /// `d` parameter of a JWK. Returns `None` for PEM keys and for JWKs
+    /// without `d`.
+    pub fn private_scalar(&self) -> Option<&[u8]> {
+        match self {
+            Self::Bytes(b) => Some(b),
+            Self::Jwk(jwk) => jwk.d.as_ref().map(AsRef::as_ref),
+            Self::Pem(_) => None,
+        }
+    }
+}
 
 define_test!(
-    "public" => public_key: ByteString,
-    "private" => private_key: ByteString,
+    "public" => public_key: EcdhKey,
+    "private" => private_key: EcdhKey,
     "shared" => shared_secret: ByteString,
 );
+
+// Decode the point of a SubjectPublicKeyInfo with a named curve
+#[cfg(feature = "num-bigint")]
+fn spki_point(curve: EllipticCurve, der: &[u8]) -> Result<ec_point::EcPoint, ec_point::PointError> {
+    use ec_point::PointError;
+
+    let spki = der::SubjectPublicKeyInfo::from_der(der).map_err(|_| PointError::InvalidAsn)?;
+    if spki.algorithm.oid != der::EC_PUBLIC_KEY_OID {
+        return Err(PointError::InvalidAsn);
+    }
+    match spki.algorithm.parameters_oid() {
+        None => return Err(PointError::UnsupportedEncoding),
+        Some(oid) if oid != curve.oid() => return Err(PointError::WrongCurve),
+        Some(_) => {}
+    }
+    ec_point::Sec1Point::decode(curve, spki.public_key)?.to_point(curve)
+}
+
+#[cfg(feature = "num-bigint")]
+impl Test {
+    /// Decode and validate the public key of a test
+    ///
+    /// On failure the error gives the reason the public key was rejected.
+    /// A DER `SubjectPublicKeyInfo` is recognized by its SEQUENCE tag, which
+    /// is never a valid SEC1 tag. A key for a curve other than that of the
+    /// group is rejected with `PointError::WrongCurve`, and a key with
+    /// explicit curve parameters with `PointError::UnsupportedEncoding`.
+    /// Groups using a binary curve are rejected with
+    /// `PointError::UnsupportedCurve`.
+    pub fn public_point(
+        &self,
+        group: &TestGroup,
+    ) -> Result<ec_point::EcPoint, ec_point::PointError> {
+        let curve = group
+            .curve
+            .prime()
+            .ok_or(ec_point::PointError::UnsupportedCurve)?;
+        let point = match &self.public_key {
+            EcdhKey::Bytes(b) if b.first() == Some(&der::TAG_SEQUENCE) => spki_point(curve, b)?,
+            EcdhKey::Bytes(b) => ec_point::Sec1Point::decode(curve, b)?.to_point(curve)?,
+            EcdhKey::Jwk(jwk) if jwk.curve != curve => {
+                return Err(ec_point::PointError::WrongCurve)
+            }
+            EcdhKey::Jwk(jwk) => jwk.point()?,
+            EcdhKey::Pem(pem) => {
+                let der = pem.to_der().map_err(|_| ec_point::PointError::InvalidAsn)?;
+                spki_point(curve, &der)?
+            }
+        };
+        point.validate(curve)?;
+        Ok(point)
+    }
+}
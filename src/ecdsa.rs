@@ -121,8 +121,40 @@ define_test_group!(
     "publicKeyJwk" => jwk: Option<EcdsaPublicJwk>,
     "publicKey" => key: EcdsaPublic,
     "publicKeyDer" => der: ByteString,
-    "publicKeyPem" => pem: String,
+    "publicKeyPem" => pem: Pem,
     "sha" => hash: HashFunction,
 );
 
 define_test!(msg: ByteString, sig: ByteString);
+
+#[cfg(feature = "digest")]
+impl Test {
+    /// Return the digest of the message, using the hash function of the group
+    pub fn digest(&self, group: &TestGroup) -> Vec<u8> {
+        group.hash.digest(&self.msg)
+    }
+}
+
+impl TestGroup {
+    /// Return the size in bytes of each of r and s in a P1363 signature
+    pub fn p1363_width(&self) -> usize {
+        (self.key.curve.order_bits() + 7) / 8
+    }
+}
+
+impl Test {
+    /// Decode the signature according to the encoding used by the group
+    pub fn signature(
+        &self,
+        group: &TestGroup,
+    ) -> Result<signature::Signature, signature::SignatureError> {
+        match group.test_type {
+            TestGroupTypeId::Ecdsa | TestGroupTypeId::EcdsaBitcoin => {
+                signature::Signature::from_der(&self.sig)
+            }
+            TestGroupTypeId::EcdsaP1363 => {
+                signature::Signature::from_p1363(&self.sig, group.p1363_width())
+            }
+        }
+    }
+}
@@ -32,7 +32,7 @@ define_test_group!(
     "publicKeyJwk" => jwk: EddsaPublicJwk,
     "publicKey" => key: EddsaPublic,
     "publicKeyDer" => der: ByteString,
-    "publicKeyPem" => pem: String,
+    "publicKeyPem" => pem: Pem,
 );
 
 define_test!(msg: ByteString, sig: ByteString);
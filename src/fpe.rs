@@ -0,0 +1,212 @@
+//! Numeral strings shared by the format preserving encryption tests
+//!
+//! The `fpe_str` tests give messages as strings over an alphabet, while the
+//! `fpe_list` tests give them as lists of integers. Both can be converted to
+//! `Numerals`, and an implementation of the `Ff1` trait can be run against
+//! either using `Test::run_ff1` from the respective module.
+
+use super::*;
+
+/// The smallest radix allowed by FF1
+pub const MIN_RADIX: usize = 2;
+
+/// The largest radix allowed by FF1
+pub const MAX_RADIX: usize = 1 << 16;
+
+/// Errors which can occur when converting a message to numerals
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum NumeralError {
+    /// The radix is outside of the range allowed by FF1
+    InvalidRadix(usize),
+    /// A character of the message does not appear in the alphabet
+    NotInAlphabet(char),
+    /// The numeral at this index is negative or not less than the radix
+    OutOfRange(usize),
+    /// The radix of the group is not the length of its alphabet
+    AlphabetMismatch { radix: usize, alphabet_len: usize },
+}
+
+impl std::fmt::Display for NumeralError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::InvalidRadix(r) => write!(f, "Invalid FF1 radix {}", r),
+            Self::NotInAlphabet(c) => write!(f, "Character {:?} is not in the alphabet", c),
+            Self::OutOfRange(i) => write!(f, "Numeral at index {} is out of range", i),
+            Self::AlphabetMismatch {
+                radix,
+                alphabet_len,
+            } => write!(
+                f,
+                "Radix {} does not match the alphabet length {}",
+                radix, alphabet_len
+            ),
+        }
+    }
+}
+
+impl std::error::Error for NumeralError {}
+
+/// A string of numerals, each less than the radix
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct Numerals {
+    radix: u32,
+    digits: Vec<u32>,
+}
+
+impl Numerals {
+    /// Create a numeral string from a list of values
+    ///
+    /// Fails if the radix is not valid for FF1, or if any value is negative
+    /// or not less than the radix.
+    pub fn from_list<T: Copy>(radix: usize, values: &[T]) -> Result<Self, NumeralError>
+    where
+        u32: TryFrom<T>,
+    {
+        if !(MIN_RADIX..=MAX_RADIX).contains(&radix) {
+            return Err(NumeralError::InvalidRadix(radix));
+        }
+
+        let digits = values
+            .iter()
+            .enumerate()
+            .map(|(i, &v)| match u32::try_from(v) {
+                Ok(d) if (d as usize) < radix => Ok(d),
+                _ => Err(NumeralError::OutOfRange(i)),
+            })
+            .collect::<Result<Vec<u32>, _>>()?;
+
+        Ok(Self {
+            radix: radix as u32,
+            digits,
+        })
+    }
+
+    /// Create a numeral string from a string over an alphabet
+    ///
+    /// The radix is the length of the alphabet, and each character maps to
+    /// its position in the alphabet.
+    pub fn from_alphabet(s: &str, alphabet: &str) -> Result<Self, NumeralError> {
+        let alphabet: Vec<char> = alphabet.chars().collect();
+        let digits = s
+            .chars()
+            .map(|c| {
+                alphabet
+                    .iter()
+                    .position(|&a| a == c)
+                    .ok_or(NumeralError::NotInAlphabet(c))
+            })
+            .collect::<Result<Vec<usize>, _>>()?;
+        Self::from_list(alphabet.len(), &digits)
+    }
+
+    /// Convert the numerals to a string over an alphabet
+    ///
+    /// Returns `None` if the alphabet is too small for the radix.
+    pub fn to_alphabet(&self, alphabet: &str) -> Option<String> {
+        let alphabet: Vec<char> = alphabet.chars().collect();
+        if alphabet.len() < self.radix as usize {
+            return None;
+        }
+        Some(self.digits.iter().map(|&d| alphabet[d as usize]).collect())
+    }
+
+    /// Return the radix
+    pub fn radix(&self) -> u32 {
+        self.radix
+    }
+
+    /// Return the numerals
+    pub fn digits(&self) -> &[u32] {
+        &self.digits
+    }
+
+    /// Return the number of numerals
+    pub fn len(&self) -> usize {
+        self.digits.len()
+    }
+
+    /// Return true if there are no numerals
+    pub fn is_empty(&self) -> bool {
+        self.digits.is_empty()
+    }
+}
+
+/// An FF1 implementation which can be run against the FPE tests
+pub trait Ff1 {
+    /// The error returned when encryption is rejected
+    type Error;
+
+    /// Encrypt a numeral string
+    ///
+    /// This should reject invalid inputs, such as an unsupported key size
+    /// or a message which is too short or too long for the radix.
+    fn ff1_encrypt(
+        &self,
+        key: &[u8],
+        tweak: &[u8],
+        radix: u32,
+        pt: &[u32],
+    ) -> Result<Vec<u32>, Self::Error>;
+}
+
+/// The result of running an FF1 implementation against a test
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum Ff1Outcome {
+    /// The implementation behaved as expected
+    Passed,
+    /// The implementation accepted an invalid input, rejected a valid one,
+    /// or produced the wrong ciphertext
+    Failed,
+    /// The plaintext cannot be given to `Ff1::ff1_encrypt`, for instance
+    /// because it contains a negative numeral or a character which is not in
+    /// the alphabet, so the implementation was not run
+    Skipped,
+}
+
+// The numerals of a list message, which may be out of range for the radix,
+// or None if some value cannot be represented as a u32
+pub(crate) fn list_digits<T: Copy>(values: &[T]) -> Option<Vec<u32>>
+where
+    u32: TryFrom<T>,
+{
+    values.iter().map(|&v| u32::try_from(v).ok()).collect()
+}
+
+// The numerals of a string message, or None if some character is not in
+// the alphabet
+pub(crate) fn alphabet_digits(s: &str, alphabet: &str) -> Option<Vec<u32>> {
+    let alphabet: Vec<char> = alphabet.chars().collect();
+    s.chars()
+        .map(|c| alphabet.iter().position(|&a| a == c).map(|d| d as u32))
+        .collect()
+}
+
+// Run ff1 on the plaintext numerals, which are passed to the implementation
+// even if they are out of range for the radix, and compare the behavior
+// with the expected result. If the radix or plaintext cannot be represented
+// the test is skipped.
+pub(crate) fn run_ff1<F: Ff1>(
+    ff1: &F,
+    key: &[u8],
+    tweak: &[u8],
+    radix: Option<u32>,
+    pt: Option<Vec<u32>>,
+    ct: Result<Numerals, NumeralError>,
+    result: TestResult,
+) -> Ff1Outcome {
+    let (radix, pt) = match (radix, pt) {
+        (Some(radix), Some(pt)) => (radix, pt),
+        _ => return Ff1Outcome::Skipped,
+    };
+
+    let passed = match ff1.ff1_encrypt(key, tweak, radix, &pt) {
+        Ok(out) => !result.must_fail() && matches!(ct, Ok(ct) if ct.digits() == out),
+        Err(_) => result.must_fail(),
+    };
+
+    if passed {
+        Ff1Outcome::Passed
+    } else {
+        Ff1Outcome::Failed
+    }
+}
@@ -51,3 +51,32 @@ define_test!(
     "msg" => pt: Vec<isize>,
     ct: Vec<usize>,
 );
+
+impl Test {
+    /// Convert the plaintext to numerals using the group radix
+    pub fn pt_numerals(&self, group: &TestGroup) -> Result<fpe::Numerals, fpe::NumeralError> {
+        fpe::Numerals::from_list(group.radix, &self.pt)
+    }
+
+    /// Convert the ciphertext to numerals using the group radix
+    pub fn ct_numerals(&self, group: &TestGroup) -> Result<fpe::Numerals, fpe::NumeralError> {
+        fpe::Numerals::from_list(group.radix, &self.ct)
+    }
+
+    /// Run an FF1 implementation against this test
+    ///
+    /// Plaintexts with numerals which are not less than the radix are still
+    /// given to the implementation, which is expected to reject them. Tests
+    /// with a negative numeral are skipped.
+    pub fn run_ff1<F: fpe::Ff1>(&self, group: &TestGroup, ff1: &F) -> fpe::Ff1Outcome {
+        fpe::run_ff1(
+            ff1,
+            &self.key,
+            &self.tweak,
+            u32::try_from(group.radix).ok(),
+            fpe::list_digits(&self.pt),
+            self.ct_numerals(group),
+            self.result,
+        )
+    }
+}
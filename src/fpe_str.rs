@@ -48,3 +48,52 @@ define_test!(
     "msg" => pt: String,
     ct: String
 );
+
+impl TestGroup {
+    /// Check that the radix of the group is the length of its alphabet
+    pub fn check_alphabet(&self) -> Result<(), fpe::NumeralError> {
+        let alphabet_len = self.alphabet.chars().count();
+        if alphabet_len == self.radix {
+            Ok(())
+        } else {
+            Err(fpe::NumeralError::AlphabetMismatch {
+                radix: self.radix,
+                alphabet_len,
+            })
+        }
+    }
+}
+
+impl Test {
+    /// Convert the plaintext to numerals using the group alphabet
+    pub fn pt_numerals(&self, group: &TestGroup) -> Result<fpe::Numerals, fpe::NumeralError> {
+        group.check_alphabet()?;
+        fpe::Numerals::from_alphabet(&self.pt, &group.alphabet)
+    }
+
+    /// Convert the ciphertext to numerals using the group alphabet
+    pub fn ct_numerals(&self, group: &TestGroup) -> Result<fpe::Numerals, fpe::NumeralError> {
+        group.check_alphabet()?;
+        fpe::Numerals::from_alphabet(&self.ct, &group.alphabet)
+    }
+
+    /// Run an FF1 implementation against this test
+    ///
+    /// Tests with a plaintext character which is not in the alphabet, or
+    /// whose group radix does not match its alphabet, are skipped.
+    pub fn run_ff1<F: fpe::Ff1>(&self, group: &TestGroup, ff1: &F) -> fpe::Ff1Outcome {
+        let radix = match group.check_alphabet() {
+            Ok(()) => u32::try_from(group.radix).ok(),
+            Err(_) => None,
+        };
+        fpe::run_ff1(
+            ff1,
+            &self.key,
+            &self.tweak,
+            radix,
+            fpe::alphabet_digits(&self.pt, &group.alphabet),
+            self.ct_numerals(group),
+            self.result,
+        )
+    }
+}
@@ -0,0 +1,175 @@
+//! JSON Web Signature, Encryption and Key tests
+//!
+//! The test data comes in four formats, so this module has a submodule for
+//! each: `signature` for JWS verification, `encryption` for JWE decryption,
+//! `key` for validation of keys and key sets, and `crypto` for a mixture
+//! of JWS and JWE tests. Keys are given as `JwkOrSet`.
+
+use super::*;
+
+/// A JWS or JWE, in either the compact or the JSON serialization
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub enum JoseObject {
+    /// The compact serialization, a sequence of base64url encoded parts
+    /// separated by periods
+    Compact(String),
+    /// The JSON serialization, as JSON text
+    Json(String),
+}
+
+impl<'de> Deserialize<'de> for JoseObject {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // The JSON serialization appears both as an object and as a string
+        match serde_json::Value::deserialize(deserializer)? {
+            serde_json::Value::String(s) if s.starts_with('{') => Ok(Self::Json(s)),
+            serde_json::Value::String(s) => Ok(Self::Compact(s)),
+            obj @ serde_json::Value::Object(_) => Ok(Self::Json(obj.to_string())),
+            _ => Err(D::Error::custom("expected a JWS or JWE")),
+        }
+    }
+}
+
+impl JoseObject {
+    /// Return the serialized object
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Compact(s) => s,
+            Self::Json(s) => s,
+        }
+    }
+
+    /// Return the parts of the compact serialization, or `None` if this uses
+    /// the JSON serialization
+    ///
+    /// A JWS has three parts and a JWE has five.
+    pub fn compact_parts(&self) -> Option<Vec<&str>> {
+        match self {
+            Self::Compact(s) => Some(s.split('.').collect()),
+            Self::Json(_) => None,
+        }
+    }
+
+    /// Return the decoded protected header of the compact serialization
+    ///
+    /// Returns `None` if this uses the JSON serialization or if the header
+    /// is not valid base64url.
+    pub fn protected_header(&self) -> Option<Vec<u8>> {
+        let parts = self.compact_parts()?;
+        data_encoding::BASE64URL_NOPAD
+            .decode(parts[0].as_bytes())
+            .ok()
+    }
+}
+
+/// JSON Web Signature verification tests
+pub mod signature {
+    use super::*;
+
+    define_test_set!(@no_algorithm "JSON Web Signature", "json_web_signature_schema_v1.json");
+
+    define_test_set_names!(
+        JsonWebSignature => "json_web_signature",
+    );
+
+    define_test_flags!(
+        AlgIsNone,
+        JsonSerialization,
+        ModifiedPadding,
+        ModifiedSignature,
+        WrongPrimitive,
+    );
+
+    define_test_group_type_id!(
+        "JsonWebSignature" => JsonWebSignature,
+    );
+
+    define_test_group!(
+        comment: String,
+        private: JwkOrSet,
+        public: Option<JwkOrSet>,
+    );
+
+    define_test!(jws: JoseObject);
+}
+
+/// JSON Web Encryption decryption tests
+pub mod encryption {
+    use super::*;
+
+    define_test_set!(@no_algorithm "JSON Web Encryption", "json_web_encryption_schema_v1.json");
+
+    define_test_set_names!(
+        JsonWebEncryption => "json_web_encryption",
+    );
+
+    define_test_flags!(
+        CompressedPlaintext,
+        JsonSerialization,
+        ModifiedPkcs15Padding,
+        Pkcs15WithOaepKey,
+        Pkcs5Padding,
+        WrongCipher,
+    );
+
+    define_test_group_type_id!(
+        "JsonWebEncryption" => JsonWebEncryption,
+    );
+
+    define_test_group!(
+        comment: String,
+        private: JwkOrSet,
+        public: Option<JwkOrSet>,
+    );
+
+    define_test!(jwe: JoseObject, enc: String, pt: Option<ByteString>);
+}
+
+/// JSON Web Key and Key Set validation tests
+pub mod key {
+    use super::*;
+
+    define_test_set!(@no_algorithm "JSON Web Key", "json_web_key_schema_v1.json");
+
+    define_test_set_names!(
+        JsonWebKey => "json_web_key",
+    );
+
+    define_test_flags!(Ambiguous, DuplicateKid, MixedKeySet);
+
+    define_test_group_type_id!(
+        "JsonWebKey" => JsonWebKey,
+    );
+
+    define_test_group!(
+        comment: String,
+        private: JwkOrSet,
+        public: Option<JwkOrSet>,
+    );
+
+    define_test!(jws: JoseObject);
+}
+
+/// Combined JSON Web Signature and Encryption tests
+pub mod crypto {
+    use super::*;
+
+    define_test_set!(@no_algorithm "JSON Web Crypto", "json_web_crypto_schema_v1.json");
+
+    define_test_set_names!(
+        JsonWebCrypto => "json_web_crypto",
+    );
+
+    define_test_flags!(JsonSerialization, JsonWebKeyset);
+
+    define_test_group_type_id!(
+        "JsonWebCrypto" => JsonWebCrypto,
+    );
+
+    define_test_group!(
+        comment: String,
+        private: JwkOrSet,
+        public: Option<JwkOrSet>,
+    );
+
+    define_test!(jws: Option<JoseObject>, jwe: Option<JoseObject>);
+}
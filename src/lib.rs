@@ -11,8 +11,19 @@
 //!
 //! Large integers (such as those used in the RSA test data) are decoded as
 //! big-endian byte arrays into a `LargeInteger` struct, which is again a light
-//! wrapper around `Vec<u8>`. Additionally if the `num-bigint` feature is enabled,
-//! this type also gains a conversion function to `num_bigint::BigUint`.
+//! wrapper around `Vec<u8>`, with helpers for fixed width and machine integer
+//! output. If the `crypto-bigint` feature is enabled it can be converted to a
+//! `crypto_bigint::Uint`. Additionally if the `num-bigint` feature is enabled,
+//! this type also gains a conversion function to `num_bigint::BigUint`, and
+//! the `ec_point` module gains reference point validation and arithmetic,
+//! which is used by `ecdh::Test::public_point`. This needs only `num-bigint`,
+//! as the curve parameters are built in even if the `ec` feature is disabled;
+//! `EllipticCurve::params` is available with either feature. Combined with `rsa_sig`, the `rsa_padding` module can recover and decode
+//! the padding of RSA signatures; decoding PSS padding also needs `digest`.
+//!
+//! If the `digest` feature is enabled, `HashFunction` can compute digests,
+//! and the signature verification tests can provide the hash of their message
+//! for use with APIs which sign or verify a precomputed digest.
 //!
 //! Each submodule of this crate includes a set of structs: a `TestName` which
 //! specifies which individual test is desired, a `TestSet` which is the set of
@@ -136,6 +147,33 @@ macro_rules! define_test_group_type_id {
     }
 }
 
+// The jose test sets have no algorithm field, so it is the only module
+// which does not use this macro
+#[cfg(any(
+    feature = "aead",
+    feature = "bls",
+    feature = "cipher",
+    feature = "daead",
+    feature = "dsa",
+    feature = "ec",
+    feature = "ecdh",
+    feature = "ecdsa",
+    feature = "eddsa",
+    feature = "fpe",
+    feature = "hkdf",
+    feature = "keywrap",
+    feature = "mac",
+    feature = "mldsa_sign",
+    feature = "mldsa_verify",
+    feature = "mlkem",
+    feature = "num-bigint",
+    feature = "pbes2",
+    feature = "pbkdf2",
+    feature = "primality",
+    feature = "rsa_enc",
+    feature = "rsa_sig",
+    feature = "xdh"
+))]
 macro_rules! define_algorithm_map {
     ( $( $json_str:expr => $enum_elem:ident ),* $(,)?) => {
         #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, serde_derive::Deserialize)]
@@ -284,7 +322,26 @@ macro_rules! define_test {
 }
 
 macro_rules! define_test_set {
-    ( $schema_type:expr, $( $schema_name:expr ),* ) => {
+    ( @no_algorithm $schema_type:expr, $( $schema_name:expr ),* ) => {
+        define_test_set!(@define [] [] $schema_type, $( $schema_name ),*);
+    };
+    ( @rsa_params $schema_type:expr, $( $schema_name:expr ),* ) => {
+        define_test_set!(@define [ pub algorithm: Algorithm, ] [ check_params ] $schema_type, $( $schema_name ),*);
+
+        impl TestSet {
+            // Check that every group has the parameters given by the name of
+            // the test set, if the name encodes them
+            fn check_params(test: TestName, obj: &Self) -> Result<(), WycheproofError> {
+                match test.params() {
+                    Some(params) if obj.test_groups.iter().any(|g| g.params() != params) => {
+                        Err(WycheproofError::InvalidData)
+                    }
+                    _ => Ok(()),
+                }
+            }
+        }
+    };
+    ( @define [ $( $algorithm_field:tt )* ] [ $( $check:ident )? ] $schema_type:expr, $( $schema_name:expr ),* ) => {
 
         #[derive(Debug, Clone, Hash, Eq, PartialEq)]
         struct TestSchema {
@@ -310,7 +367,7 @@ macro_rules! define_test_set {
         #[derive(Debug, Clone, Eq, PartialEq, serde_derive::Deserialize)]
         #[serde(deny_unknown_fields)]
         pub struct TestSet {
-            pub algorithm: Algorithm,
+            $( $algorithm_field )*
             #[serde(rename = "generatorVersion")]
             pub generator_version: Option<String>,
             #[serde(rename = "numberOfTests")]
@@ -336,12 +393,19 @@ macro_rules! define_test_set {
 
             pub fn load(test: TestName) -> Result<Self, WycheproofError> {
                 match serde_json::from_str(test.json_data()) {
-                    Ok(set) => Self::check(set),
+                    Ok(set) => {
+                        let set = Self::check(set)?;
+                        $( Self::$check(test, &set)?; )?
+                        Ok(set)
+                    }
                     Err(e) => Err(WycheproofError::ParsingFailed(Box::new(e))),
                 }
             }
         }
     };
+    ( $schema_type:expr, $( $schema_name:expr ),* ) => {
+        define_test_set!(@define [ pub algorithm: Algorithm, ] [] $schema_type, $( $schema_name ),*);
+    };
 }
 
 /// The expected result of a Wycheproof test
@@ -420,6 +484,170 @@ pub enum EllipticCurve {
     Brainpool512t1,
 }
 
+impl EllipticCurve {
+    /// Return a list of all supported curves
+    pub fn all() -> Vec<EllipticCurve> {
+        vec![
+            Self::Secp160r1,
+            Self::Secp160r2,
+            Self::Secp160k1,
+            Self::Secp192r1,
+            Self::Secp192k1,
+            Self::Secp224r1,
+            Self::Secp256r1,
+            Self::Secp384r1,
+            Self::Secp521r1,
+            Self::Secp224k1,
+            Self::Secp256k1,
+            Self::Brainpool224r1,
+            Self::Brainpool256r1,
+            Self::Brainpool320r1,
+            Self::Brainpool384r1,
+            Self::Brainpool512r1,
+            Self::Brainpool224t1,
+            Self::Brainpool256t1,
+            Self::Brainpool320t1,
+            Self::Brainpool384t1,
+            Self::Brainpool512t1,
+        ]
+    }
+
+    // Returns (name, oid, field bits, order bits)
+    //
+    // This table is maintained by hand; with the `ec` feature the tests
+    // cross-check it against the domain parameters of the EC curve data set.
+    fn info(&self) -> (&'static str, &'static str, usize, usize) {
+        match self {
+            Self::Secp160r1 => ("secp160r1", "1.3.132.0.8", 160, 161),
+            Self::Secp160r2 => ("secp160r2", "1.3.132.0.30", 160, 161),
+            Self::Secp160k1 => ("secp160k1", "1.3.132.0.9", 160, 161),
+            Self::Secp192r1 => ("secp192r1", "1.2.840.10045.3.1.1", 192, 192),
+            Self::Secp192k1 => ("secp192k1", "1.3.132.0.31", 192, 192),
+            Self::Secp224r1 => ("secp224r1", "1.3.132.0.33", 224, 224),
+            Self::Secp256r1 => ("secp256r1", "1.2.840.10045.3.1.7", 256, 256),
+            Self::Secp384r1 => ("secp384r1", "1.3.132.0.34", 384, 384),
+            Self::Secp521r1 => ("secp521r1", "1.3.132.0.35", 521, 521),
+            Self::Secp224k1 => ("secp224k1", "1.3.132.0.32", 224, 225),
+            Self::Secp256k1 => ("secp256k1", "1.3.132.0.10", 256, 256),
+            Self::Brainpool224r1 => ("brainpoolP224r1", "1.3.36.3.3.2.8.1.1.5", 224, 224),
+            Self::Brainpool256r1 => ("brainpoolP256r1", "1.3.36.3.3.2.8.1.1.7", 256, 256),
+            Self::Brainpool320r1 => ("brainpoolP320r1", "1.3.36.3.3.2.8.1.1.9", 320, 320),
+            Self::Brainpool384r1 => ("brainpoolP384r1", "1.3.36.3.3.2.8.1.1.11", 384, 384),
+            Self::Brainpool512r1 => ("brainpoolP512r1", "1.3.36.3.3.2.8.1.1.13", 512, 512),
+            Self::Brainpool224t1 => ("brainpoolP224t1", "1.3.36.3.3.2.8.1.1.6", 224, 224),
+            Self::Brainpool256t1 => ("brainpoolP256t1", "1.3.36.3.3.2.8.1.1.8", 256, 256),
+            Self::Brainpool320t1 => ("brainpoolP320t1", "1.3.36.3.3.2.8.1.1.10", 320, 320),
+            Self::Brainpool384t1 => ("brainpoolP384t1", "1.3.36.3.3.2.8.1.1.12", 384, 384),
+            Self::Brainpool512t1 => ("brainpoolP512t1", "1.3.36.3.3.2.8.1.1.14", 512, 512),
+        }
+    }
+
+    /// Return the name of the curve as used in the Wycheproof data
+    pub fn name(&self) -> &'static str {
+        self.info().0
+    }
+
+    /// Return the object identifier of the curve, in dotted decimal form
+    pub fn oid(&self) -> &'static str {
+        self.info().1
+    }
+
+    /// Return the curve which has the given (dotted decimal) object identifier
+    pub fn from_oid(oid: &str) -> Option<Self> {
+        Self::all().into_iter().find(|c| c.oid() == oid)
+    }
+
+    /// Return the size of the field prime in bits
+    pub fn field_bits(&self) -> usize {
+        self.info().2
+    }
+
+    /// Return the size of the group order in bits
+    pub fn order_bits(&self) -> usize {
+        self.info().3
+    }
+
+    /// Return the name of the curve as used by JWK (RFC 7518 and RFC 8812),
+    /// or `None` if the curve has no registered name
+    pub fn jwk_name(&self) -> Option<&'static str> {
+        match self {
+            Self::Secp256r1 => Some("P-256"),
+            Self::Secp384r1 => Some("P-384"),
+            Self::Secp521r1 => Some("P-521"),
+            Self::Secp256k1 => Some("secp256k1"),
+            _ => None,
+        }
+    }
+}
+
+/// Elliptic curves over binary fields
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, serde_derive::Deserialize)]
+pub enum BinaryCurve {
+    #[serde(rename = "sect283k1")]
+    Sect283k1,
+    #[serde(rename = "sect283r1")]
+    Sect283r1,
+    #[serde(rename = "sect409k1")]
+    Sect409k1,
+    #[serde(rename = "sect409r1")]
+    Sect409r1,
+    #[serde(rename = "sect571k1")]
+    Sect571k1,
+    #[serde(rename = "sect571r1")]
+    Sect571r1,
+}
+
+impl BinaryCurve {
+    /// Return a list of all supported curves
+    pub fn all() -> Vec<BinaryCurve> {
+        vec![
+            Self::Sect283k1,
+            Self::Sect283r1,
+            Self::Sect409k1,
+            Self::Sect409r1,
+            Self::Sect571k1,
+            Self::Sect571r1,
+        ]
+    }
+
+    // Returns (name, oid, field degree, order bits)
+    fn info(&self) -> (&'static str, &'static str, usize, usize) {
+        match self {
+            Self::Sect283k1 => ("sect283k1", "1.3.132.0.16", 283, 281),
+            Self::Sect283r1 => ("sect283r1", "1.3.132.0.17", 283, 282),
+            Self::Sect409k1 => ("sect409k1", "1.3.132.0.36", 409, 407),
+            Self::Sect409r1 => ("sect409r1", "1.3.132.0.37", 409, 409),
+            Self::Sect571k1 => ("sect571k1", "1.3.132.0.38", 571, 570),
+            Self::Sect571r1 => ("sect571r1", "1.3.132.0.39", 571, 570),
+        }
+    }
+
+    /// Return the name of the curve as used in the Wycheproof data
+    pub fn name(&self) -> &'static str {
+        self.info().0
+    }
+
+    /// Return the object identifier of the curve, in dotted decimal form
+    pub fn oid(&self) -> &'static str {
+        self.info().1
+    }
+
+    /// Return the curve which has the given (dotted decimal) object identifier
+    pub fn from_oid(oid: &str) -> Option<Self> {
+        Self::all().into_iter().find(|c| c.oid() == oid)
+    }
+
+    /// Return the degree m of the field GF(2^m)
+    pub fn field_bits(&self) -> usize {
+        self.info().2
+    }
+
+    /// Return the size of the group order in bits
+    pub fn order_bits(&self) -> usize {
+        self.info().3
+    }
+}
+
 /// Hash Function identifiers
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, serde_derive::Deserialize)]
 pub enum HashFunction {
@@ -457,6 +685,231 @@ pub enum HashFunction {
     Shake256,
 }
 
+impl HashFunction {
+    /// Return a list of all hash functions
+    pub fn all() -> Vec<HashFunction> {
+        vec![
+            Self::Sha1,
+            Self::Sha2_224,
+            Self::Sha2_256,
+            Self::Sha2_384,
+            Self::Sha2_512,
+            Self::Sha2_512_224,
+            Self::Sha2_512_256,
+            Self::Sha3_224,
+            Self::Sha3_256,
+            Self::Sha3_384,
+            Self::Sha3_512,
+            Self::Shake128,
+            Self::Shake256,
+        ]
+    }
+
+    /// Return the name of the hash function as used in the Wycheproof data
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Sha1 => "SHA-1",
+            Self::Sha2_224 => "SHA-224",
+            Self::Sha2_256 => "SHA-256",
+            Self::Sha2_384 => "SHA-384",
+            Self::Sha2_512 => "SHA-512",
+            Self::Sha2_512_224 => "SHA-512/224",
+            Self::Sha2_512_256 => "SHA-512/256",
+            Self::Sha3_224 => "SHA3-224",
+            Self::Sha3_256 => "SHA3-256",
+            Self::Sha3_384 => "SHA3-384",
+            Self::Sha3_512 => "SHA3-512",
+            Self::Shake128 => "SHAKE128",
+            Self::Shake256 => "SHAKE256",
+        }
+    }
+
+    /// Return true if this is an extendable output function
+    pub fn is_xof(&self) -> bool {
+        matches!(self, Self::Shake128 | Self::Shake256)
+    }
+
+    /// Return the output length in bytes
+    ///
+    /// For SHAKE128 and SHAKE256 this is the output length used by the
+    /// signature schemes in the test data (RFC 8692 and RFC 8702), namely
+    /// 32 and 64 bytes respectively.
+    pub fn output_len(&self) -> usize {
+        match self {
+            Self::Sha1 => 20,
+            Self::Sha2_224 => 28,
+            Self::Sha2_256 => 32,
+            Self::Sha2_384 => 48,
+            Self::Sha2_512 => 64,
+            Self::Sha2_512_224 => 28,
+            Self::Sha2_512_256 => 32,
+            Self::Sha3_224 => 28,
+            Self::Sha3_256 => 32,
+            Self::Sha3_384 => 48,
+            Self::Sha3_512 => 64,
+            Self::Shake128 => 32,
+            Self::Shake256 => 64,
+        }
+    }
+
+    /// Return the internal block length (or sponge rate) in bytes
+    pub fn block_len(&self) -> usize {
+        match self {
+            Self::Sha1 | Self::Sha2_224 | Self::Sha2_256 => 64,
+            Self::Sha2_384 | Self::Sha2_512 | Self::Sha2_512_224 | Self::Sha2_512_256 => 128,
+            Self::Sha3_224 => 144,
+            Self::Sha3_256 => 136,
+            Self::Sha3_384 => 104,
+            Self::Sha3_512 => 72,
+            Self::Shake128 => 168,
+            Self::Shake256 => 136,
+        }
+    }
+
+    /// Return the object identifier of the hash, in dotted decimal form
+    pub fn oid(&self) -> &'static str {
+        match self {
+            Self::Sha1 => "1.3.14.3.2.26",
+            Self::Sha2_224 => "2.16.840.1.101.3.4.2.4",
+            Self::Sha2_256 => "2.16.840.1.101.3.4.2.1",
+            Self::Sha2_384 => "2.16.840.1.101.3.4.2.2",
+            Self::Sha2_512 => "2.16.840.1.101.3.4.2.3",
+            Self::Sha2_512_224 => "2.16.840.1.101.3.4.2.5",
+            Self::Sha2_512_256 => "2.16.840.1.101.3.4.2.6",
+            Self::Sha3_224 => "2.16.840.1.101.3.4.2.7",
+            Self::Sha3_256 => "2.16.840.1.101.3.4.2.8",
+            Self::Sha3_384 => "2.16.840.1.101.3.4.2.9",
+            Self::Sha3_512 => "2.16.840.1.101.3.4.2.10",
+            Self::Shake128 => "2.16.840.1.101.3.4.2.11",
+            Self::Shake256 => "2.16.840.1.101.3.4.2.12",
+        }
+    }
+
+    /// Look up a hash function by its object identifier
+    pub fn from_oid(oid: &str) -> Option<Self> {
+        Self::all().into_iter().find(|h| h.oid() == oid)
+    }
+
+    /// Return the object identifier of HMAC using this hash, in dotted
+    /// decimal form, or `None` for the extendable output functions
+    pub fn hmac_oid(&self) -> Option<&'static str> {
+        match self {
+            Self::Sha1 => Some("1.2.840.113549.2.7"),
+            Self::Sha2_224 => Some("1.2.840.113549.2.8"),
+            Self::Sha2_256 => Some("1.2.840.113549.2.9"),
+            Self::Sha2_384 => Some("1.2.840.113549.2.10"),
+            Self::Sha2_512 => Some("1.2.840.113549.2.11"),
+            Self::Sha2_512_224 => Some("1.2.840.113549.2.12"),
+            Self::Sha2_512_256 => Some("1.2.840.113549.2.13"),
+            Self::Sha3_224 => Some("2.16.840.1.101.3.4.2.13"),
+            Self::Sha3_256 => Some("2.16.840.1.101.3.4.2.14"),
+            Self::Sha3_384 => Some("2.16.840.1.101.3.4.2.15"),
+            Self::Sha3_512 => Some("2.16.840.1.101.3.4.2.16"),
+            Self::Shake128 | Self::Shake256 => None,
+        }
+    }
+
+    /// Return the JWA name of RSASSA-PKCS1-v1_5 using this hash, or `None`
+    /// if JOSE does not define one
+    pub fn jwa_pkcs1_alg(&self) -> Option<&'static str> {
+        match self {
+            Self::Sha2_256 => Some("RS256"),
+            Self::Sha2_384 => Some("RS384"),
+            Self::Sha2_512 => Some("RS512"),
+            _ => None,
+        }
+    }
+
+    /// Return the DER prefix of the PKCS #1 v1.5 `DigestInfo` structure
+    ///
+    /// The encoded `DigestInfo` is this prefix followed by the digest. This
+    /// uses the form where the algorithm parameters are an explicit NULL.
+    /// Returns `None` for the SHAKE functions, which are not used with PKCS #1
+    /// v1.5 signatures.
+    pub fn pkcs1_digest_info_prefix(&self) -> Option<&'static [u8]> {
+        match self {
+            Self::Sha1 => Some(&[
+                0x30, 0x21, 0x30, 0x09, 0x06, 0x05, 0x2b, 0x0e, 0x03, 0x02, 0x1a, 0x05, 0x00, 0x04,
+                0x14,
+            ]),
+            Self::Sha2_224 => Some(&[
+                0x30, 0x2d, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02,
+                0x04, 0x05, 0x00, 0x04, 0x1c,
+            ]),
+            Self::Sha2_256 => Some(&[
+                0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02,
+                0x01, 0x05, 0x00, 0x04, 0x20,
+            ]),
+            Self::Sha2_384 => Some(&[
+                0x30, 0x41, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02,
+                0x02, 0x05, 0x00, 0x04, 0x30,
+            ]),
+            Self::Sha2_512 => Some(&[
+                0x30, 0x51, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02,
+                0x03, 0x05, 0x00, 0x04, 0x40,
+            ]),
+            Self::Sha2_512_224 => Some(&[
+                0x30, 0x2d, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02,
+                0x05, 0x05, 0x00, 0x04, 0x1c,
+            ]),
+            Self::Sha2_512_256 => Some(&[
+                0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02,
+                0x06, 0x05, 0x00, 0x04, 0x20,
+            ]),
+            Self::Sha3_224 => Some(&[
+                0x30, 0x2d, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02,
+                0x07, 0x05, 0x00, 0x04, 0x1c,
+            ]),
+            Self::Sha3_256 => Some(&[
+                0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02,
+                0x08, 0x05, 0x00, 0x04, 0x20,
+            ]),
+            Self::Sha3_384 => Some(&[
+                0x30, 0x41, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02,
+                0x09, 0x05, 0x00, 0x04, 0x30,
+            ]),
+            Self::Sha3_512 => Some(&[
+                0x30, 0x51, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02,
+                0x0a, 0x05, 0x00, 0x04, 0x40,
+            ]),
+            Self::Shake128 | Self::Shake256 => None,
+        }
+    }
+
+    /// Compute the digest of `msg`
+    ///
+    /// For the SHAKE functions the output is `output_len` bytes long.
+    #[cfg(feature = "digest")]
+    pub fn digest(&self, msg: &[u8]) -> Vec<u8> {
+        use sha2::Digest;
+        use sha3::digest::{ExtendableOutput, Update, XofReader};
+
+        fn xof<H: Default + Update + ExtendableOutput>(msg: &[u8], len: usize) -> Vec<u8> {
+            let mut h = H::default();
+            h.update(msg);
+            let mut out = vec![0u8; len];
+            h.finalize_xof().read(&mut out);
+            out
+        }
+
+        match self {
+            Self::Sha1 => sha1::Sha1::digest(msg).to_vec(),
+            Self::Sha2_224 => sha2::Sha224::digest(msg).to_vec(),
+            Self::Sha2_256 => sha2::Sha256::digest(msg).to_vec(),
+            Self::Sha2_384 => sha2::Sha384::digest(msg).to_vec(),
+            Self::Sha2_512 => sha2::Sha512::digest(msg).to_vec(),
+            Self::Sha2_512_224 => sha2::Sha512_224::digest(msg).to_vec(),
+            Self::Sha2_512_256 => sha2::Sha512_256::digest(msg).to_vec(),
+            Self::Sha3_224 => sha3::Sha3_224::digest(msg).to_vec(),
+            Self::Sha3_256 => sha3::Sha3_256::digest(msg).to_vec(),
+            Self::Sha3_384 => sha3::Sha3_384::digest(msg).to_vec(),
+            Self::Sha3_512 => sha3::Sha3_512::digest(msg).to_vec(),
+            Self::Shake128 => xof::<sha3::Shake128>(msg, self.output_len()),
+            Self::Shake256 => xof::<sha3::Shake256>(msg, self.output_len()),
+        }
+    }
+}
+
 /// MGF identifiers
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, serde_derive::Deserialize)]
 pub enum Mgf {
@@ -468,6 +921,55 @@ pub enum Mgf {
     Shake256,
 }
 
+impl Mgf {
+    /// Return the name of the MGF as used in the Wycheproof data
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Mgf1 => "MGF1",
+            Self::Shake128 => "SHAKE128",
+            Self::Shake256 => "SHAKE256",
+        }
+    }
+
+    /// Return true if the MGF is an extendable output function used directly
+    pub fn is_xof(&self) -> bool {
+        matches!(self, Self::Shake128 | Self::Shake256)
+    }
+
+    /// Return the object identifier of the MGF, in dotted decimal form
+    ///
+    /// MGF1 is parameterized by a hash function which is identified
+    /// separately. The SHAKE functions are identified by their own OIDs.
+    pub fn oid(&self) -> &'static str {
+        match self {
+            Self::Mgf1 => "1.2.840.113549.1.1.8",
+            Self::Shake128 => HashFunction::Shake128.oid(),
+            Self::Shake256 => HashFunction::Shake256.oid(),
+        }
+    }
+}
+
+/// RSA padding parameters, as encoded in the name of a test set
+///
+/// For OAEP there is no salt and the parameters are never embedded in the key.
+/// For PSS with SHAKE the MGF is the XOF itself, so there is no MGF hash.
+#[cfg(any(feature = "rsa_enc", feature = "rsa_sig"))]
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub struct RsaParams {
+    /// Size of the modulus in bits
+    pub modulus_bits: usize,
+    /// Hash function applied to the message or label
+    pub hash: HashFunction,
+    /// Mask generation function
+    pub mgf: Mgf,
+    /// Hash function used by MGF1
+    pub mgf_hash: Option<HashFunction>,
+    /// PSS salt length in bytes
+    pub salt_len: Option<usize>,
+    /// True if the PSS parameters are included in the public key
+    pub with_params: bool,
+}
+
 /// Edwards curves
 #[cfg(feature = "eddsa")]
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, serde_derive::Deserialize)]
@@ -478,6 +980,25 @@ pub enum EdwardsCurve {
     Ed448,
 }
 
+#[cfg(feature = "eddsa")]
+impl EdwardsCurve {
+    /// Return the name of the curve as used by JWK (RFC 8037)
+    pub fn jwk_name(&self) -> &'static str {
+        match self {
+            Self::Ed25519 => "Ed25519",
+            Self::Ed448 => "Ed448",
+        }
+    }
+
+    /// Return the key size in bits, as given in the Wycheproof data
+    pub fn key_bits(&self) -> usize {
+        match self {
+            Self::Ed25519 => 255,
+            Self::Ed448 => 448,
+        }
+    }
+}
+
 /// Montgomery curves
 #[cfg(feature = "xdh")]
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, serde_derive::Deserialize)]
@@ -531,6 +1052,165 @@ impl AsRef<[u8]> for ByteString {
     }
 }
 
+/// Errors which can occur when decoding PEM
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum PemError {
+    /// The `-----BEGIN` line was missing or malformed
+    InvalidHeader,
+    /// The `-----END` line was missing, malformed, or had a different label
+    InvalidFooter,
+    /// The body was not valid base64
+    InvalidBase64,
+    /// The label was not the one expected
+    UnexpectedLabel,
+    /// The decoded DER did not match the DER given alongside it
+    DerMismatch,
+}
+
+impl std::fmt::Display for PemError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::InvalidHeader => write!(f, "Invalid PEM header"),
+            Self::InvalidFooter => write!(f, "Invalid PEM footer"),
+            Self::InvalidBase64 => write!(f, "Invalid base64 in PEM body"),
+            Self::UnexpectedLabel => write!(f, "Unexpected PEM label"),
+            Self::DerMismatch => write!(f, "PEM does not match the DER encoding"),
+        }
+    }
+}
+
+impl std::error::Error for PemError {}
+
+/// A PEM encoded value, in the strict form of RFC 7468
+#[derive(Clone, Hash, Eq, PartialEq, serde_derive::Deserialize)]
+#[serde(transparent)]
+pub struct Pem {
+    value: String,
+}
+
+impl Pem {
+    // Split into the label and the base64 lines of the body
+    fn parts(&self) -> Result<(&str, Vec<&str>), PemError> {
+        let mut lines = self.value.trim_end_matches('\n').split('\n');
+
+        let label = lines
+            .next()
+            .and_then(|l| l.strip_prefix("-----BEGIN "))
+            .and_then(|l| l.strip_suffix("-----"))
+            .ok_or(PemError::InvalidHeader)?;
+
+        let mut body: Vec<&str> = lines.collect();
+        let footer = body.pop().ok_or(PemError::InvalidFooter)?;
+        if footer != format!("-----END {}-----", label) {
+            return Err(PemError::InvalidFooter);
+        }
+        Ok((label, body))
+    }
+
+    /// Return the PEM text
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+
+    /// Return the label, such as `PUBLIC KEY`
+    pub fn label(&self) -> Result<&str, PemError> {
+        self.parts().map(|(label, _)| label)
+    }
+
+    /// Decode the base64 body, returning the DER encoding
+    pub fn to_der(&self) -> Result<Vec<u8>, PemError> {
+        let (_, body) = self.parts()?;
+        data_encoding::BASE64
+            .decode(body.concat().as_bytes())
+            .map_err(|_| PemError::InvalidBase64)
+    }
+
+    /// Check that the PEM has the expected label and encodes exactly `der`
+    pub fn check(&self, label: &str, der: &[u8]) -> Result<(), PemError> {
+        if self.label()? != label {
+            return Err(PemError::UnexpectedLabel);
+        }
+        if self.to_der()? != der {
+            return Err(PemError::DerMismatch);
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Pem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.value)
+    }
+}
+
+impl fmt::Display for Pem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.value)
+    }
+}
+
+impl std::ops::Deref for Pem {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl AsRef<str> for Pem {
+    fn as_ref(&self) -> &str {
+        &self.value
+    }
+}
+
+// A key of the key agreement tests, which is given as a hex string, PEM
+// text or a JWK object depending on the test group. The public key enums of
+// the ecdh and xdh modules are deserialized through this.
+#[cfg(any(feature = "ecdh", feature = "xdh"))]
+pub(crate) enum EncodedKey<J> {
+    Bytes(ByteString),
+    Jwk(J),
+    Pem(Pem),
+}
+
+#[cfg(any(feature = "ecdh", feature = "xdh"))]
+impl<'de, J: Deserialize<'de>> Deserialize<'de> for EncodedKey<J> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct KeyVisitor<J>(std::marker::PhantomData<J>);
+
+        impl<'de, J: Deserialize<'de>> serde::de::Visitor<'de> for KeyVisitor<J> {
+            type Value = EncodedKey<J>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a hex string, PEM text or JWK")
+            }
+
+            fn visit_str<E: Error>(self, s: &str) -> Result<Self::Value, E> {
+                if s.starts_with("-----BEGIN") {
+                    return Ok(EncodedKey::Pem(Pem {
+                        value: s.to_owned(),
+                    }));
+                }
+                let value = data_encoding::HEXLOWER
+                    .decode(s.as_bytes())
+                    .map_err(E::custom)?;
+                Ok(EncodedKey::Bytes(ByteString { value }))
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                map: A,
+            ) -> Result<Self::Value, A::Error> {
+                let jwk =
+                    Deserialize::deserialize(serde::de::value::MapAccessDeserializer::new(map))?;
+                Ok(EncodedKey::Jwk(jwk))
+            }
+        }
+
+        deserializer.deserialize_any(KeyVisitor(std::marker::PhantomData))
+    }
+}
+
 #[derive(Debug, Clone, Hash, Eq, PartialEq, serde_derive::Deserialize)]
 #[serde(transparent)]
 pub struct LargeInteger {
@@ -557,10 +1237,132 @@ impl LargeInteger {
         Self { value }
     }
 
+    // The minimal two's complement encoding of an unsigned value, which is
+    // the form used for integers in the Wycheproof data
+    #[cfg(any(feature = "ecdsa", feature = "rsa_enc", feature = "rsa_sig"))]
+    fn from_unsigned(value: &[u8]) -> Self {
+        let skip = value.iter().take_while(|&&b| b == 0).count();
+        let value = &value[skip..];
+
+        let mut out = Vec::with_capacity(value.len() + 1);
+        if value.first().map_or(true, |&b| b >= 0x80) {
+            out.push(0x00);
+        }
+        out.extend_from_slice(value);
+        Self::new(out)
+    }
+
+    // The big-endian encoding with any leading zero bytes removed
+    fn trimmed(&self) -> &[u8] {
+        let skip = self.value.iter().take_while(|&&b| b == 0).count();
+        &self.value[skip..]
+    }
+
+    /// Return the big-endian encoding left padded to exactly `len` bytes
+    ///
+    /// Any leading zero bytes, such as the sign byte of the Wycheproof
+    /// encoding, are removed first. Returns `None` if the value does not fit.
+    pub fn to_be_bytes_padded(&self, len: usize) -> Option<Vec<u8>> {
+        let trimmed = self.trimmed();
+        if trimmed.len() > len {
+            return None;
+        }
+        let mut out = vec![0u8; len - trimmed.len()];
+        out.extend_from_slice(trimmed);
+        Some(out)
+    }
+
+    /// Return the length of the value in bits
+    pub fn bits(&self) -> usize {
+        let trimmed = self.trimmed();
+        trimmed
+            .first()
+            .map_or(0, |&b| 8 * trimmed.len() - b.leading_zeros() as usize)
+    }
+
+    /// Return the value as a `u64`, or `None` if it does not fit
+    pub fn to_u64(&self) -> Option<u64> {
+        let bytes = self.to_be_bytes_padded(8)?;
+        Some(u64::from_be_bytes(bytes.try_into().ok()?))
+    }
+
+    /// Return the value as a `u128`, or `None` if it does not fit
+    pub fn to_u128(&self) -> Option<u128> {
+        let bytes = self.to_be_bytes_padded(16)?;
+        Some(u128::from_be_bytes(bytes.try_into().ok()?))
+    }
+
     #[cfg(feature = "num-bigint")]
     pub fn as_num_bigint(&self) -> num_bigint::BigUint {
         num_bigint::BigUint::from_bytes_be(&self.value)
     }
+
+    /// Return the value as a fixed width `crypto_bigint::Uint`, or `None`
+    /// if it does not fit
+    #[cfg(feature = "crypto-bigint")]
+    pub fn as_crypto_bigint<const LIMBS: usize>(&self) -> Option<crypto_bigint::Uint<LIMBS>> {
+        let bytes = self.to_be_bytes_padded(crypto_bigint::Uint::<LIMBS>::BYTES)?;
+        Some(crypto_bigint::Uint::from_be_slice(&bytes))
+    }
+}
+
+/// The tests of a password based test set, divided according to an
+/// iteration budget
+///
+/// Tests with an iteration count over the budget are skipped, except that
+/// the cheapest of them are retained where needed to ensure every flag which
+/// occurs in the test set is still covered by at least one test.
+#[cfg(any(feature = "pbes2", feature = "pbkdf2"))]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct IterationBudget<'a, T> {
+    /// Tests with an iteration count no greater than the budget
+    pub within_budget: Vec<&'a T>,
+    /// Tests over the budget which were kept to cover a flag
+    pub retained: Vec<&'a T>,
+    /// Tests over the budget which were skipped
+    pub skipped: Vec<&'a T>,
+}
+
+#[cfg(any(feature = "pbes2", feature = "pbkdf2"))]
+impl<'a, T> IterationBudget<'a, T> {
+    fn select<F: PartialEq>(
+        tests: impl Iterator<Item = &'a T>,
+        max_iterations: usize,
+        iterations: impl Fn(&T) -> usize,
+        flags: impl Fn(&T) -> &[F],
+    ) -> Self {
+        let (within_budget, mut over_budget): (Vec<&T>, Vec<&T>) =
+            tests.partition(|t| iterations(t) <= max_iterations);
+        over_budget.sort_by_key(|t| iterations(t));
+
+        let mut covered: Vec<&F> = within_budget.iter().flat_map(|t| flags(t)).collect();
+        let mut retained = Vec::new();
+        let mut skipped = Vec::new();
+
+        for t in over_budget {
+            if flags(t).iter().any(|f| !covered.contains(&f)) {
+                covered.extend(flags(t));
+                retained.push(t);
+            } else {
+                skipped.push(t);
+            }
+        }
+
+        Self {
+            within_budget,
+            retained,
+            skipped,
+        }
+    }
+
+    /// Return the tests which should be run, those within the budget along
+    /// with those retained to cover a flag
+    pub fn tests(&self) -> impl Iterator<Item = &'a T> + '_ {
+        self.within_budget
+            .iter()
+            .chain(self.retained.iter())
+            .copied()
+    }
 }
 
 mod test_keys;
@@ -570,15 +1372,41 @@ pub use test_keys::*;
 #[cfg(feature = "aead")]
 pub mod aead;
 
+#[cfg(feature = "bls")]
+pub mod bls;
+
 #[cfg(feature = "cipher")]
 pub mod cipher;
 
+#[cfg(any(
+    feature = "dsa",
+    feature = "ecdh",
+    feature = "ecdsa",
+    feature = "mldsa_sign",
+    feature = "mldsa_verify",
+    feature = "pbes2",
+    feature = "pbkdf2",
+    feature = "rsa_enc",
+    feature = "rsa_sig"
+))]
+pub mod der;
+
+#[cfg(feature = "daead")]
+pub mod daead;
+
 #[cfg(feature = "dsa")]
 pub mod dsa;
 
 #[cfg(feature = "ec")]
 pub mod ec_curve;
 
+// Point validation needs the curve parameters even without the `ec` tests
+#[cfg(all(feature = "num-bigint", not(feature = "ec")))]
+#[allow(dead_code)]
+mod ec_curve;
+
+pub mod ec_point;
+
 #[cfg(feature = "ecdh")]
 pub mod ecdh;
 
@@ -588,6 +1416,9 @@ pub mod ecdsa;
 #[cfg(feature = "eddsa")]
 pub mod eddsa;
 
+#[cfg(feature = "fpe")]
+pub mod fpe;
+
 #[cfg(feature = "fpe")]
 pub mod fpe_list;
 
@@ -597,6 +1428,9 @@ pub mod fpe_str;
 #[cfg(feature = "hkdf")]
 pub mod hkdf;
 
+#[cfg(feature = "jose")]
+pub mod jose;
+
 #[cfg(feature = "keywrap")]
 pub mod keywrap;
 
@@ -624,12 +1458,21 @@ pub mod rsa_oaep;
 #[cfg(feature = "rsa_enc")]
 pub mod rsa_pkcs1_decrypt;
 
+#[cfg(feature = "rsa_sig")]
+pub mod rsa_pkcs1_sign;
+
 #[cfg(feature = "rsa_sig")]
 pub mod rsa_pkcs1_verify;
 
 #[cfg(feature = "rsa_sig")]
 pub mod rsa_pss_verify;
 
+#[cfg(all(feature = "rsa_sig", feature = "num-bigint"))]
+pub mod rsa_padding;
+
+#[cfg(any(feature = "dsa", feature = "ecdsa"))]
+pub mod signature;
+
 #[cfg(feature = "xdh")]
 pub mod xdh;
 
@@ -23,6 +23,17 @@ define_algorithm_map!(
     "ML-DSA-87" => MlDsa87,
 );
 
+impl Algorithm {
+    /// Return the object identifier of the algorithm, in dotted decimal form
+    pub fn oid(&self) -> &'static str {
+        match self {
+            Self::MlDsa44 => der::ML_DSA_44_OID,
+            Self::MlDsa65 => der::ML_DSA_65_OID,
+            Self::MlDsa87 => der::ML_DSA_87_OID,
+        }
+    }
+}
+
 define_test_flags!(
     BoundaryCondition,
     IncorrectPrivateKeyLength,
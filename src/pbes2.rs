@@ -40,6 +40,111 @@ define_algorithm_map!(
     "PbeWithHmacSha512AndAes_256" => PbeWithHmacSha512AndAes256,
 );
 
+/// The object identifier of PBES2 (RFC 8018), in dotted decimal form
+pub const PBES2_OID: &str = "1.2.840.113549.1.5.13";
+
+pub use crate::der::PBKDF2_OID;
+
+/// The block cipher mode used by a PBES2 encryption scheme
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum CipherMode {
+    /// CBC mode with PKCS #7 padding
+    Cbc,
+}
+
+impl Algorithm {
+    /// Return the hash function used with HMAC as the PBKDF2 pseudorandom function
+    pub fn prf(&self) -> HashFunction {
+        match self {
+            Self::PbeWithHmacSha1AndAes128
+            | Self::PbeWithHmacSha1AndAes192
+            | Self::PbeWithHmacSha1AndAes256 => HashFunction::Sha1,
+            Self::PbeWithHmacSha224AndAes128
+            | Self::PbeWithHmacSha224AndAes192
+            | Self::PbeWithHmacSha224AndAes256 => HashFunction::Sha2_224,
+            Self::PbeWithHmacSha256AndAes128
+            | Self::PbeWithHmacSha256AndAes192
+            | Self::PbeWithHmacSha256AndAes256 => HashFunction::Sha2_256,
+            Self::PbeWithHmacSha384AndAes128
+            | Self::PbeWithHmacSha384AndAes192
+            | Self::PbeWithHmacSha384AndAes256 => HashFunction::Sha2_384,
+            Self::PbeWithHmacSha512AndAes128
+            | Self::PbeWithHmacSha512AndAes192
+            | Self::PbeWithHmacSha512AndAes256 => HashFunction::Sha2_512,
+        }
+    }
+
+    /// Return the object identifier of the pseudorandom function
+    pub fn prf_oid(&self) -> &'static str {
+        self.prf().hmac_oid().expect("HMAC PRF")
+    }
+
+    /// Return the length of the AES key in bytes
+    pub fn cipher_key_len(&self) -> usize {
+        match self {
+            Self::PbeWithHmacSha1AndAes128
+            | Self::PbeWithHmacSha224AndAes128
+            | Self::PbeWithHmacSha256AndAes128
+            | Self::PbeWithHmacSha384AndAes128
+            | Self::PbeWithHmacSha512AndAes128 => 16,
+            Self::PbeWithHmacSha1AndAes192
+            | Self::PbeWithHmacSha224AndAes192
+            | Self::PbeWithHmacSha256AndAes192
+            | Self::PbeWithHmacSha384AndAes192
+            | Self::PbeWithHmacSha512AndAes192 => 24,
+            Self::PbeWithHmacSha1AndAes256
+            | Self::PbeWithHmacSha224AndAes256
+            | Self::PbeWithHmacSha256AndAes256
+            | Self::PbeWithHmacSha384AndAes256
+            | Self::PbeWithHmacSha512AndAes256 => 32,
+        }
+    }
+
+    /// Return the block cipher mode of the encryption scheme
+    pub fn cipher_mode(&self) -> CipherMode {
+        match self {
+            Self::PbeWithHmacSha1AndAes128
+            | Self::PbeWithHmacSha1AndAes192
+            | Self::PbeWithHmacSha1AndAes256
+            | Self::PbeWithHmacSha224AndAes128
+            | Self::PbeWithHmacSha224AndAes192
+            | Self::PbeWithHmacSha224AndAes256
+            | Self::PbeWithHmacSha256AndAes128
+            | Self::PbeWithHmacSha256AndAes192
+            | Self::PbeWithHmacSha256AndAes256
+            | Self::PbeWithHmacSha384AndAes128
+            | Self::PbeWithHmacSha384AndAes192
+            | Self::PbeWithHmacSha384AndAes256
+            | Self::PbeWithHmacSha512AndAes128
+            | Self::PbeWithHmacSha512AndAes192
+            | Self::PbeWithHmacSha512AndAes256 => CipherMode::Cbc,
+        }
+    }
+
+    /// Return the object identifier of the encryption scheme
+    ///
+    /// This is AES in the mode given by `cipher_mode`.
+    pub fn cipher_oid(&self) -> &'static str {
+        match self {
+            Self::PbeWithHmacSha1AndAes128
+            | Self::PbeWithHmacSha224AndAes128
+            | Self::PbeWithHmacSha256AndAes128
+            | Self::PbeWithHmacSha384AndAes128
+            | Self::PbeWithHmacSha512AndAes128 => "2.16.840.1.101.3.4.1.2",
+            Self::PbeWithHmacSha1AndAes192
+            | Self::PbeWithHmacSha224AndAes192
+            | Self::PbeWithHmacSha256AndAes192
+            | Self::PbeWithHmacSha384AndAes192
+            | Self::PbeWithHmacSha512AndAes192 => "2.16.840.1.101.3.4.1.22",
+            Self::PbeWithHmacSha1AndAes256
+            | Self::PbeWithHmacSha224AndAes256
+            | Self::PbeWithHmacSha256AndAes256
+            | Self::PbeWithHmacSha384AndAes256
+            | Self::PbeWithHmacSha512AndAes256 => "2.16.840.1.101.3.4.1.42",
+        }
+    }
+}
+
 define_test_flags!(Ascii, BmpString, NonUtf8, Printable, Utf8,);
 
 define_test_group_type_id!(
@@ -56,3 +161,48 @@ define_test!(
     msg: ByteString,
     ct: ByteString,
 );
+
+impl Test {
+    /// Return the DER encoded `PBES2-params` for this test
+    ///
+    /// The PBKDF2 parameters omit the optional key length, and omit the
+    /// pseudorandom function when it is the default of HMAC-SHA-1.
+    pub fn pbes2_params(&self, algorithm: Algorithm) -> Vec<u8> {
+        let iterations = (self.iteration_count as u64).to_be_bytes();
+        let salt = der::encode(der::TAG_OCTET_STRING, &self.salt);
+        let count = der::encode_unsigned_integer(&iterations);
+
+        let pbkdf2_params = if algorithm.prf() == HashFunction::Sha1 {
+            der::encode_sequence(&[&salt, &count])
+        } else {
+            let null = der::encode(der::TAG_NULL, &[]);
+            let prf = der::encode_sequence(&[&der::encode_oid(algorithm.prf_oid()), &null]);
+            der::encode_sequence(&[&salt, &count, &prf])
+        };
+        let kdf = der::encode_sequence(&[&der::encode_oid(PBKDF2_OID), &pbkdf2_params]);
+
+        let iv = der::encode(der::TAG_OCTET_STRING, &self.iv);
+        let cipher = der::encode_sequence(&[&der::encode_oid(algorithm.cipher_oid()), &iv]);
+
+        der::encode_sequence(&[&kdf, &cipher])
+    }
+
+    /// Return the DER encoded PBES2 `AlgorithmIdentifier` for this test
+    pub fn algorithm_identifier(&self, algorithm: Algorithm) -> Vec<u8> {
+        der::encode_sequence(&[&der::encode_oid(PBES2_OID), &self.pbes2_params(algorithm)])
+    }
+}
+
+impl TestSet {
+    /// Divide the tests according to a maximum iteration count
+    ///
+    /// See `IterationBudget` for how tests over the budget are handled.
+    pub fn with_iteration_budget(&self, max_iterations: usize) -> IterationBudget<'_, Test> {
+        IterationBudget::select(
+            self.test_groups.iter().flat_map(|g| g.tests.iter()),
+            max_iterations,
+            |t| t.iteration_count,
+            |t| &t.flags,
+        )
+    }
+}
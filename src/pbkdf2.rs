@@ -20,6 +20,26 @@ define_algorithm_map!(
     "PBKDF2-HMACSHA512" => Pbkdf2HmacSha512,
 );
 
+pub use crate::der::PBKDF2_OID;
+
+impl Algorithm {
+    /// Return the hash function used with HMAC as the pseudorandom function
+    pub fn prf(&self) -> HashFunction {
+        match self {
+            Self::Pbkdf2HmacSha1 => HashFunction::Sha1,
+            Self::Pbkdf2HmacSha224 => HashFunction::Sha2_224,
+            Self::Pbkdf2HmacSha256 => HashFunction::Sha2_256,
+            Self::Pbkdf2HmacSha384 => HashFunction::Sha2_384,
+            Self::Pbkdf2HmacSha512 => HashFunction::Sha2_512,
+        }
+    }
+
+    /// Return the object identifier of the pseudorandom function
+    pub fn prf_oid(&self) -> &'static str {
+        self.prf().hmac_oid().expect("HMAC PRF")
+    }
+}
+
 define_test_flags!(
     Ascii,
     LargeIterationCount,
@@ -44,3 +64,17 @@ define_test!(
     "dkLen" => dk_len: usize,
     dk: ByteString,
 );
+
+impl TestSet {
+    /// Divide the tests according to a maximum iteration count
+    ///
+    /// See `IterationBudget` for how tests over the budget are handled.
+    pub fn with_iteration_budget(&self, max_iterations: usize) -> IterationBudget<'_, Test> {
+        IterationBudget::select(
+            self.test_groups.iter().flat_map(|g| g.tests.iter()),
+            max_iterations,
+            |t| t.iteration_count,
+            |t| &t.flags,
+        )
+    }
+}
@@ -2,15 +2,8 @@
 
 use super::*;
 
-define_test_set!("RSA OAEP decrypt", "rsaes_oaep_decrypt_schema_v1.json");
+define_test_set!(@rsa_params "RSA OAEP decrypt", "rsaes_oaep_decrypt_schema_v1.json");
 
-/*
-Currently skips:
-
-rsa_three_primes_oaep_2048_sha1_mgf1sha1_test.json
-rsa_three_primes_oaep_3072_sha224_mgf1sha224_test.json
-rsa_three_primes_oaep_4096_sha256_mgf1sha256_test.json
-*/
 define_test_set_names!(
     Rsa2048Sha1Mgf1Sha1 => "rsa_oaep_2048_sha1_mgf1sha1",
     Rsa2048Sha224Mgf1Sha1 => "rsa_oaep_2048_sha224_mgf1sha1",
@@ -34,8 +27,107 @@ define_test_set_names!(
     Rsa4096Sha512Mgf1Sha1 => "rsa_oaep_4096_sha512_mgf1sha1",
     Rsa4096Sha512Mgf1Sha512 => "rsa_oaep_4096_sha512_mgf1sha512",
     RsaMisc => "rsa_oaep_misc",
+    RsaThreePrimes2048Sha1Mgf1Sha1 => "rsa_three_primes_oaep_2048_sha1_mgf1sha1",
+    RsaThreePrimes3072Sha224Mgf1Sha224 => "rsa_three_primes_oaep_3072_sha224_mgf1sha224",
+    RsaThreePrimes4096Sha256Mgf1Sha256 => "rsa_three_primes_oaep_4096_sha256_mgf1sha256",
 );
 
+impl TestName {
+    /// Return the parameters encoded in the name of the test set
+    ///
+    /// Returns `None` for sets which mix several configurations.
+    pub fn params(&self) -> Option<RsaParams> {
+        fn oaep(modulus_bits: usize, hash: HashFunction, mgf_hash: HashFunction) -> RsaParams {
+            RsaParams {
+                modulus_bits,
+                hash,
+                mgf: Mgf::Mgf1,
+                mgf_hash: Some(mgf_hash),
+                salt_len: None,
+                with_params: false,
+            }
+        }
+
+        match self {
+            Self::Rsa2048Sha1Mgf1Sha1 => Some(oaep(2048, HashFunction::Sha1, HashFunction::Sha1)),
+            Self::Rsa2048Sha224Mgf1Sha1 => {
+                Some(oaep(2048, HashFunction::Sha2_224, HashFunction::Sha1))
+            }
+            Self::Rsa2048Sha224Mgf1Sha224 => {
+                Some(oaep(2048, HashFunction::Sha2_224, HashFunction::Sha2_224))
+            }
+            Self::Rsa2048Sha256Mgf1Sha1 => {
+                Some(oaep(2048, HashFunction::Sha2_256, HashFunction::Sha1))
+            }
+            Self::Rsa2048Sha256Mgf1Sha256 => {
+                Some(oaep(2048, HashFunction::Sha2_256, HashFunction::Sha2_256))
+            }
+            Self::Rsa2048Sha384Mgf1Sha1 => {
+                Some(oaep(2048, HashFunction::Sha2_384, HashFunction::Sha1))
+            }
+            Self::Rsa2048Sha384Mgf1Sha384 => {
+                Some(oaep(2048, HashFunction::Sha2_384, HashFunction::Sha2_384))
+            }
+            Self::Rsa2048Sha512Mgf1Sha1 => {
+                Some(oaep(2048, HashFunction::Sha2_512, HashFunction::Sha1))
+            }
+            Self::Rsa2048Sha512Mgf1Sha512 => {
+                Some(oaep(2048, HashFunction::Sha2_512, HashFunction::Sha2_512))
+            }
+            Self::Rsa2048Sha512_224Mgf1Sha1 => {
+                Some(oaep(2048, HashFunction::Sha2_512_224, HashFunction::Sha1))
+            }
+            Self::Rsa2048Sha512_224Mgf1Sha512_224 => Some(oaep(
+                2048,
+                HashFunction::Sha2_512_224,
+                HashFunction::Sha2_512_224,
+            )),
+            Self::Rsa3072Sha256Mgf1Sha1 => {
+                Some(oaep(3072, HashFunction::Sha2_256, HashFunction::Sha1))
+            }
+            Self::Rsa3072Sha256Mgf1Sha256 => {
+                Some(oaep(3072, HashFunction::Sha2_256, HashFunction::Sha2_256))
+            }
+            Self::Rsa3072Sha512Mgf1Sha1 => {
+                Some(oaep(3072, HashFunction::Sha2_512, HashFunction::Sha1))
+            }
+            Self::Rsa3072Sha512Mgf1Sha512 => {
+                Some(oaep(3072, HashFunction::Sha2_512, HashFunction::Sha2_512))
+            }
+            Self::Rsa3072Sha512_256Mgf1Sha1 => {
+                Some(oaep(3072, HashFunction::Sha2_512_256, HashFunction::Sha1))
+            }
+            Self::Rsa3072Sha512_256Mgf1Sha512_256 => Some(oaep(
+                3072,
+                HashFunction::Sha2_512_256,
+                HashFunction::Sha2_512_256,
+            )),
+            Self::Rsa4096Sha256Mgf1Sha1 => {
+                Some(oaep(4096, HashFunction::Sha2_256, HashFunction::Sha1))
+            }
+            Self::Rsa4096Sha256Mgf1Sha256 => {
+                Some(oaep(4096, HashFunction::Sha2_256, HashFunction::Sha2_256))
+            }
+            Self::Rsa4096Sha512Mgf1Sha1 => {
+                Some(oaep(4096, HashFunction::Sha2_512, HashFunction::Sha1))
+            }
+            Self::Rsa4096Sha512Mgf1Sha512 => {
+                Some(oaep(4096, HashFunction::Sha2_512, HashFunction::Sha2_512))
+            }
+            Self::RsaMisc => None,
+            Self::RsaThreePrimes2048Sha1Mgf1Sha1 => {
+                Some(oaep(2048, HashFunction::Sha1, HashFunction::Sha1))
+            }
+            Self::RsaThreePrimes3072Sha224Mgf1Sha224 => {
+                Some(oaep(3072, HashFunction::Sha2_224, HashFunction::Sha2_224))
+            }
+            Self::RsaThreePrimes4096Sha256Mgf1Sha256 => {
+                Some(oaep(4096, HashFunction::Sha2_256, HashFunction::Sha2_256))
+            }
+        }
+    }
+}
+
 define_algorithm_map!("RSAES-OAEP" => RsaOaep);
 
 define_test_flags!(
@@ -59,8 +151,36 @@ define_test_group!(
     "mgfSha" => mgf_hash: HashFunction,
     "privateKeyJwk" => jwk: Option<RsaPrivateJwk>,
     "privateKeyPkcs8" => pkcs8: ByteString,
-    "privateKeyPem" => pem: String,
+    "privateKeyPem" => pem: Pem,
     "sha" => hash: HashFunction,
 );
 
 define_test!("msg" => pt: ByteString, ct: ByteString, label: ByteString);
+
+impl TestGroup {
+    /// Return the parameters of this group
+    pub fn params(&self) -> RsaParams {
+        RsaParams {
+            modulus_bits: self.key_size,
+            hash: self.hash,
+            mgf: self.mgf,
+            mgf_hash: Some(self.mgf_hash),
+            salt_len: None,
+            with_params: false,
+        }
+    }
+
+    /// Return the JWK `alg` parameter for this group, if JOSE defines one
+    pub fn jwk_alg(&self) -> Option<&'static str> {
+        if self.mgf != Mgf::Mgf1 || self.mgf_hash != self.hash {
+            return None;
+        }
+        match self.hash {
+            HashFunction::Sha1 => Some("RSA-OAEP"),
+            HashFunction::Sha2_256 => Some("RSA-OAEP-256"),
+            HashFunction::Sha2_384 => Some("RSA-OAEP-384"),
+            HashFunction::Sha2_512 => Some("RSA-OAEP-512"),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,363 @@
+//! Reference decoding of RSA signature padding
+//!
+//! These functions recover the encoded message from an RSA signature and
+//! decode its padding, reporting each part of the structure rather than
+//! simply accepting or rejecting it. This makes it possible to check why an
+//! invalid test vector is invalid. They are simple reference implementations
+//! and are neither fast nor constant time.
+//!
+//! The module requires the `rsa_sig` and `num-bigint` features. Decoding PSS
+//! signatures also requires the `digest` feature, since unmasking the
+//! encoding and checking its hash need the hash functions.
+
+use super::*;
+use crate::der::{DerError, Reader};
+use num_bigint::BigUint;
+
+/// Errors which can occur when recovering the encoded message of a signature
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum RecoverError {
+    /// The signature is not the same length as the modulus
+    InvalidLength { expected: usize, actual: usize },
+    /// The signature, as an integer, is not less than the modulus
+    SignatureOutOfRange,
+}
+
+impl std::fmt::Display for RecoverError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::InvalidLength { expected, actual } => write!(
+                f,
+                "Invalid RSA signature length {} (expected {})",
+                actual, expected
+            ),
+            Self::SignatureOutOfRange => write!(f, "RSA signature is not less than the modulus"),
+        }
+    }
+}
+
+impl std::error::Error for RecoverError {}
+
+impl RsaPublic {
+    /// Return the size of the modulus in bits
+    pub fn modulus_bits(&self) -> usize {
+        self.n.as_num_bigint().bits() as usize
+    }
+
+    /// Return the size of the modulus in bytes
+    pub fn modulus_len(&self) -> usize {
+        (self.modulus_bits() + 7) / 8
+    }
+
+    /// Recover the encoded message from a signature by computing sig^e mod n
+    ///
+    /// This is RSAVP1 from RFC 8017, including its length and range checks.
+    /// The result is the same length as the modulus.
+    pub fn recover(&self, sig: &[u8]) -> Result<Vec<u8>, RecoverError> {
+        let k = self.modulus_len();
+        if sig.len() != k {
+            return Err(RecoverError::InvalidLength {
+                expected: k,
+                actual: sig.len(),
+            });
+        }
+
+        let n = self.n.as_num_bigint();
+        let s = BigUint::from_bytes_be(sig);
+        if s >= n {
+            return Err(RecoverError::SignatureOutOfRange);
+        }
+
+        let m = s.modpow(&self.e.as_num_bigint(), &n);
+        Ok(LargeInteger::new(m.to_bytes_be())
+            .to_be_bytes_padded(k)
+            .expect("m < n"))
+    }
+}
+
+/// A decoded PKCS #1 v1.5 `DigestInfo`
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct DigestInfo {
+    /// The hash algorithm OID, in dotted decimal form
+    pub algorithm: String,
+    /// The complete encoding of the algorithm parameters, if present
+    pub parameters: Option<Vec<u8>>,
+    /// The digest
+    pub digest: Vec<u8>,
+}
+
+impl DigestInfo {
+    fn decode(reader: &mut Reader) -> Result<Self, DerError> {
+        let mut seq = Reader::new(reader.read(der::TAG_SEQUENCE)?);
+
+        let mut alg_id = Reader::new(seq.read(der::TAG_SEQUENCE)?);
+        let algorithm = alg_id.read_oid()?;
+        let parameters = if alg_id.is_empty() {
+            None
+        } else {
+            Some(alg_id.read_raw()?.to_vec())
+        };
+        alg_id.finish()?;
+
+        let digest = seq.read(der::TAG_OCTET_STRING)?.to_vec();
+        seq.finish()?;
+
+        Ok(Self {
+            algorithm,
+            parameters,
+            digest,
+        })
+    }
+}
+
+/// The decoded structure of a PKCS #1 v1.5 signature encoding
+///
+/// A correct encoding is `00 || 01 || PS || 00 || T` where PS is at least
+/// eight 0xff bytes and T is the DER encoded `DigestInfo`.
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct Pkcs1v15Decoding {
+    /// The first byte of the encoding, which should be zero
+    pub leading_byte: u8,
+    /// The block type, which should be 1 for signatures
+    pub block_type: u8,
+    /// The number of padding bytes preceding the separator
+    pub padding_len: usize,
+    /// True if every padding byte is 0xff
+    pub padding_is_ff: bool,
+    /// True if a zero byte separates the padding from the `DigestInfo`
+    pub has_separator: bool,
+    /// The decoded `DigestInfo`, or the reason it could not be decoded
+    pub digest_info: Result<DigestInfo, DerError>,
+    /// Any bytes following the `DigestInfo`
+    pub trailing: Vec<u8>,
+}
+
+impl Pkcs1v15Decoding {
+    /// Decode a recovered PKCS #1 v1.5 signature encoding
+    pub fn decode(em: &[u8]) -> Self {
+        let leading_byte = em.first().copied().unwrap_or(0);
+        let block_type = em.get(1).copied().unwrap_or(0);
+        let body = em.get(2..).unwrap_or(&[]);
+
+        // The padding ends at the first zero byte, so that a corrupted
+        // padding byte is reported by padding_is_ff
+        let padding_len = body.iter().take_while(|&&b| b != 0x00).count();
+        let padding_is_ff = body[..padding_len].iter().all(|&b| b == 0xff);
+        let has_separator = body.get(padding_len) == Some(&0x00);
+
+        let t = if has_separator {
+            &body[padding_len + 1..]
+        } else {
+            &body[padding_len..]
+        };
+
+        let mut reader = Reader::new(t);
+        let digest_info = DigestInfo::decode(&mut reader);
+        let trailing = if digest_info.is_ok() {
+            reader.rest().to_vec()
+        } else {
+            Vec::new()
+        };
+
+        Self {
+            leading_byte,
+            block_type,
+            padding_len,
+            padding_is_ff,
+            has_separator,
+            digest_info,
+            trailing,
+        }
+    }
+
+    /// Return true if this is a correctly formed encoding using `hash`
+    ///
+    /// This checks the structure, including that the `DigestInfo` uses the
+    /// OID of `hash` with explicit NULL parameters and a digest of the right
+    /// length, but does not check the digest itself.
+    pub fn is_valid_for(&self, hash: HashFunction) -> bool {
+        let digest_info_ok = match &self.digest_info {
+            Ok(di) => {
+                di.algorithm == hash.oid()
+                    && di.parameters.as_deref() == Some(&[der::TAG_NULL, 0x00])
+                    && di.digest.len() == hash.output_len()
+            }
+            Err(_) => false,
+        };
+
+        self.leading_byte == 0x00
+            && self.block_type == 0x01
+            && self.padding_len >= 8
+            && self.padding_is_ff
+            && self.has_separator
+            && digest_info_ok
+            && self.trailing.is_empty()
+    }
+}
+
+impl rsa_pkcs1_verify::Test {
+    /// Recover and decode the PKCS #1 v1.5 encoding of the signature
+    pub fn decode_signature(
+        &self,
+        group: &rsa_pkcs1_verify::TestGroup,
+    ) -> Result<Pkcs1v15Decoding, RecoverError> {
+        Ok(Pkcs1v15Decoding::decode(&group.key.recover(&self.sig)?))
+    }
+}
+
+#[cfg(feature = "digest")]
+fn mask(mgf: Mgf, mgf_hash: Option<HashFunction>, seed: &[u8], len: usize) -> Vec<u8> {
+    match mgf {
+        Mgf::Mgf1 => {
+            let hash = mgf_hash.expect("MGF1 requires a hash function");
+            let mut out = Vec::with_capacity(len + hash.output_len());
+            let mut counter: u32 = 0;
+            while out.len() < len {
+                let input = [seed, &counter.to_be_bytes()].concat();
+                out.extend(hash.digest(&input));
+                counter += 1;
+            }
+            out.truncate(len);
+            out
+        }
+        Mgf::Shake128 | Mgf::Shake256 => {
+            use sha3::digest::{ExtendableOutput, Update, XofReader};
+
+            let mut out = vec![0u8; len];
+            if mgf == Mgf::Shake128 {
+                let mut h = sha3::Shake128::default();
+                h.update(seed);
+                h.finalize_xof().read(&mut out);
+            } else {
+                let mut h = sha3::Shake256::default();
+                h.update(seed);
+                h.finalize_xof().read(&mut out);
+            }
+            out
+        }
+    }
+}
+
+/// The decoded structure of an RSA-PSS signature encoding
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+#[cfg(feature = "digest")]
+pub struct PssDecoding {
+    /// True if the bits of the encoding above the encoded message length are zero
+    pub leftmost_bits_clear: bool,
+    /// The final byte of the encoding, which should be 0xbc
+    pub trailer: u8,
+    /// The salt length, or `None` if the unmasked data block did not have
+    /// the expected form of zero bytes followed by 0x01
+    pub salt_len: Option<usize>,
+    /// True if the hash in the encoding matches that computed from the
+    /// message and salt
+    pub hash_matches: bool,
+}
+
+#[cfg(feature = "digest")]
+impl PssDecoding {
+    /// Decode a recovered RSA-PSS signature encoding
+    ///
+    /// This follows EMSA-PSS-VERIFY from RFC 8017, with the SHAKE based
+    /// variants of RFC 8702. `em` is the output of `RsaPublic::recover`.
+    /// If `em` is too short for the modulus nothing is decoded, and
+    /// `leftmost_bits_clear` is false.
+    pub fn decode(
+        em: &[u8],
+        modulus_bits: usize,
+        msg: &[u8],
+        hash: HashFunction,
+        mgf: Mgf,
+        mgf_hash: Option<HashFunction>,
+    ) -> Self {
+        let em_bits = modulus_bits.saturating_sub(1);
+        let em_len = (em_bits + 7) / 8;
+        let h_len = hash.output_len();
+
+        // An encoding too short for the modulus cannot be decoded at all
+        if em_len == 0 || em.len() < em_len {
+            return Self {
+                leftmost_bits_clear: false,
+                trailer: em.last().copied().unwrap_or(0),
+                salt_len: None,
+                hash_matches: false,
+            };
+        }
+
+        // The recovered value may have one more byte than the encoded message
+        let (high, em) = em.split_at(em.len() - em_len);
+        let unused_bits = 8 * em_len - em_bits;
+        let top_mask = 0xffu8.checked_shl(8 - unused_bits as u32).unwrap_or(0);
+        let leftmost_bits_clear = high.iter().all(|&b| b == 0) && em[0] & top_mask == 0;
+
+        let trailer = em[em_len - 1];
+
+        if em_len < h_len + 2 {
+            return Self {
+                leftmost_bits_clear,
+                trailer,
+                salt_len: None,
+                hash_matches: false,
+            };
+        }
+
+        let (masked_db, rest) = em.split_at(em_len - h_len - 1);
+        let h = &rest[..h_len];
+
+        let mut db: Vec<u8> = masked_db
+            .iter()
+            .zip(mask(mgf, mgf_hash, h, masked_db.len()))
+            .map(|(x, y)| x ^ y)
+            .collect();
+        db[0] &= !top_mask;
+
+        let zeros = db.iter().take_while(|&&b| b == 0).count();
+        let salt_len = match db.get(zeros) {
+            Some(0x01) => Some(db.len() - zeros - 1),
+            _ => None,
+        };
+
+        let hash_matches = match salt_len {
+            Some(salt_len) => {
+                let salt = &db[db.len() - salt_len..];
+                let m_prime = [&[0u8; 8], &hash.digest(msg)[..], salt].concat();
+                hash.digest(&m_prime) == h
+            }
+            None => false,
+        };
+
+        Self {
+            leftmost_bits_clear,
+            trailer,
+            salt_len,
+            hash_matches,
+        }
+    }
+
+    /// Return true if this is a correctly formed encoding with the given salt length
+    pub fn is_valid_for(&self, salt_len: usize) -> bool {
+        self.leftmost_bits_clear
+            && self.trailer == 0xbc
+            && self.salt_len == Some(salt_len)
+            && self.hash_matches
+    }
+}
+
+#[cfg(feature = "digest")]
+impl rsa_pss_verify::Test {
+    /// Recover and decode the RSA-PSS encoding of the signature
+    pub fn decode_signature(
+        &self,
+        group: &rsa_pss_verify::TestGroup,
+    ) -> Result<PssDecoding, RecoverError> {
+        let em = group.key.recover(&self.sig)?;
+        Ok(PssDecoding::decode(
+            &em,
+            group.key.modulus_bits(),
+            &self.msg,
+            group.hash,
+            group.mgf,
+            group.mgf_hash,
+        ))
+    }
+}
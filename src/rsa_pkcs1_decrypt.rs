@@ -32,7 +32,12 @@ define_test_group!(
     "keySize" => key_size: usize,
     "privateKeyJwk" => jwk: Option<RsaPrivateJwk>,
     "privateKeyPkcs8" => pkcs8: ByteString,
-    "privateKeyPem" => pem: String,
+    "privateKeyPem" => pem: Pem,
 );
 
 define_test!("msg" => pt: ByteString, ct: ByteString);
+
+impl TestGroup {
+    /// The JWK `alg` parameter of every group
+    pub const JWK_ALG: &'static str = "RSA1_5";
+}
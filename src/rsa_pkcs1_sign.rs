@@ -0,0 +1,43 @@
+//! RSA PKCS1v1.5 signature generation tests
+
+use super::*;
+
+define_test_set!("RSA PKCS1 sign", "rsassa_pkcs1_generate_schema_v1.json");
+
+define_algorithm_map!("RSASSA-PKCS1-v1_5" => RsaPkcs1v15);
+
+define_test_set_names!(
+    Rsa1024 => "rsa_pkcs1_1024_sig_gen",
+    Rsa1536 => "rsa_pkcs1_1536_sig_gen",
+    Rsa2048 => "rsa_pkcs1_2048_sig_gen",
+    Rsa3072 => "rsa_pkcs1_3072_sig_gen",
+    Rsa4096 => "rsa_pkcs1_4096_sig_gen",
+);
+
+define_test_flags!(SmallModulus, SmallPublicKey, WeakHash);
+
+define_test_group_type_id!(
+    "RsassaPkcs1Generate" => RsaPkcs1Sign,
+);
+
+define_test_group!(
+    "privateKey" => key: RsaPrivateNoCrt,
+    "keyAsn" => asn_key: ByteString,
+    "keyDer" => der: ByteString,
+    "keyPem" => pem: Pem,
+    "keyJwk" => jwk: Option<RsaPublicJwk>,
+    "privateKeyPkcs8" => pkcs8: ByteString,
+    "privateKeyPem" => private_pem: Pem,
+    "privateKeyJwk" => private_jwk: Option<RsaPrivateJwk>,
+    "keySize" => key_size: usize,
+    "sha" => hash: HashFunction,
+);
+
+define_test!(msg: ByteString, sig: ByteString);
+
+impl TestGroup {
+    /// Return the JWK `alg` parameter for this group, if JOSE defines one
+    pub fn jwk_alg(&self) -> Option<&'static str> {
+        self.hash.jwa_pkcs1_alg()
+    }
+}
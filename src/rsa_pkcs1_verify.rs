@@ -59,9 +59,24 @@ define_test_group!(
     "publicKeyAsn" => asn_key: ByteString,
     "publicKeyDer" => der: ByteString,
     "keyJwk" => jwk: Option<RsaPublicJwk>,
-    "publicKeyPem" => pem: String,
+    "publicKeyPem" => pem: Pem,
     "keySize" => key_size: usize,
     "sha" => hash: HashFunction,
 );
 
 define_test!(msg: ByteString, sig: ByteString);
+
+impl TestGroup {
+    /// Return the JWK `alg` parameter for this group, if JOSE defines one
+    pub fn jwk_alg(&self) -> Option<&'static str> {
+        self.hash.jwa_pkcs1_alg()
+    }
+}
+
+#[cfg(feature = "digest")]
+impl Test {
+    /// Return the digest of the message, using the hash function of the group
+    pub fn digest(&self, group: &TestGroup) -> Vec<u8> {
+        group.hash.digest(&self.msg)
+    }
+}
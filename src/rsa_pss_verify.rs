@@ -1,8 +1,12 @@
 //! RSA PSS verification tests
 
 use super::*;
+use crate::der::{
+    DerError, Reader, SubjectPublicKeyInfo, RSA_ENCRYPTION_OID, TAG_NULL, TAG_SEQUENCE,
+};
 
 define_test_set!(
+    @rsa_params
     "RSA PKCS1 verify",
     "rsassa_pss_verify_schema_v1.json",
     "rsassa_pss_with_parameters_verify_schema.json"
@@ -37,6 +41,185 @@ define_test_set_names!(
     RsaPssmisc => "rsa_pss_misc",
 );
 
+impl TestName {
+    /// Return the parameters encoded in the name of the test set
+    ///
+    /// The SHAKE sets use the salt lengths fixed by RFC 8702. Returns `None`
+    /// for sets which mix several configurations.
+    pub fn params(&self) -> Option<RsaParams> {
+        fn mgf1(
+            modulus_bits: usize,
+            hash: HashFunction,
+            mgf_hash: HashFunction,
+            salt_len: usize,
+            with_params: bool,
+        ) -> RsaParams {
+            RsaParams {
+                modulus_bits,
+                hash,
+                mgf: Mgf::Mgf1,
+                mgf_hash: Some(mgf_hash),
+                salt_len: Some(salt_len),
+                with_params,
+            }
+        }
+
+        fn shake(modulus_bits: usize, mgf: Mgf) -> RsaParams {
+            let (hash, salt_len) = match mgf {
+                Mgf::Shake128 => (HashFunction::Shake128, 32),
+                _ => (HashFunction::Shake256, 64),
+            };
+            RsaParams {
+                modulus_bits,
+                hash,
+                mgf,
+                mgf_hash: None,
+                salt_len: Some(salt_len),
+                with_params: false,
+            }
+        }
+
+        match self {
+            Self::RsaPss2048Sha1Mgf1SaltLen20WithParams => {
+                Some(mgf1(2048, HashFunction::Sha1, HashFunction::Sha1, 20, true))
+            }
+            Self::RsaPss2048Sha1Mgf1SaltLen20 => Some(mgf1(
+                2048,
+                HashFunction::Sha1,
+                HashFunction::Sha1,
+                20,
+                false,
+            )),
+            Self::RsaPss2048Sha256Mgf1SaltLen0WithParams => Some(mgf1(
+                2048,
+                HashFunction::Sha2_256,
+                HashFunction::Sha2_256,
+                0,
+                true,
+            )),
+            Self::RsaPss2048Sha256Mgf1SaltLen0 => Some(mgf1(
+                2048,
+                HashFunction::Sha2_256,
+                HashFunction::Sha2_256,
+                0,
+                false,
+            )),
+            Self::RsaPss2048Sha256Mgf1SaltLen32WithParams => Some(mgf1(
+                2048,
+                HashFunction::Sha2_256,
+                HashFunction::Sha2_256,
+                32,
+                true,
+            )),
+            Self::RsaPss2048Sha256Mgf1SaltLen32 => Some(mgf1(
+                2048,
+                HashFunction::Sha2_256,
+                HashFunction::Sha2_256,
+                32,
+                false,
+            )),
+            Self::RsaPss2048Sha256Mgf1Sha1_20 => Some(mgf1(
+                2048,
+                HashFunction::Sha2_256,
+                HashFunction::Sha1,
+                20,
+                false,
+            )),
+            Self::RsaPss2048Sha384Mgf1SaltLen48 => Some(mgf1(
+                2048,
+                HashFunction::Sha2_384,
+                HashFunction::Sha2_384,
+                48,
+                false,
+            )),
+            Self::RsaPss2048Sha512_224Mgf1SaltLen28 => Some(mgf1(
+                2048,
+                HashFunction::Sha2_512_224,
+                HashFunction::Sha2_512_224,
+                28,
+                false,
+            )),
+            Self::RsaPss2048Sha512_256Mgf1SaltLen32 => Some(mgf1(
+                2048,
+                HashFunction::Sha2_512_256,
+                HashFunction::Sha2_512_256,
+                32,
+                false,
+            )),
+            Self::RsaPss2048Sha512Mgf1Sha256SaltLen32WithParams => Some(mgf1(
+                2048,
+                HashFunction::Sha2_512,
+                HashFunction::Sha2_256,
+                32,
+                true,
+            )),
+            Self::RsaPss2048Shake128 => Some(shake(2048, Mgf::Shake128)),
+            Self::RsaPss2048Shake256 => Some(shake(2048, Mgf::Shake256)),
+            Self::RsaPss3072Sha256Mgf1SaltLen32WithParams => Some(mgf1(
+                3072,
+                HashFunction::Sha2_256,
+                HashFunction::Sha2_256,
+                32,
+                true,
+            )),
+            Self::RsaPss3072Sha256Mgf1SaltLen32 => Some(mgf1(
+                3072,
+                HashFunction::Sha2_256,
+                HashFunction::Sha2_256,
+                32,
+                false,
+            )),
+            Self::RsaPss3072Shake128 => Some(shake(3072, Mgf::Shake128)),
+            Self::RsaPss3072Shake256 => Some(shake(3072, Mgf::Shake256)),
+            Self::RsaPss4096Sha256Mgf1SaltLen32 => Some(mgf1(
+                4096,
+                HashFunction::Sha2_256,
+                HashFunction::Sha2_256,
+                32,
+                false,
+            )),
+            Self::RsaPss4096Sha384Mgf1SaltLen48 => Some(mgf1(
+                4096,
+                HashFunction::Sha2_384,
+                HashFunction::Sha2_384,
+                48,
+                false,
+            )),
+            Self::RsaPss4096Sha512Mgf1SaltLen32WithParams => Some(mgf1(
+                4096,
+                HashFunction::Sha2_512,
+                HashFunction::Sha2_512,
+                32,
+                true,
+            )),
+            Self::RsaPss4096Sha512Mgf1SaltLen32 => Some(mgf1(
+                4096,
+                HashFunction::Sha2_512,
+                HashFunction::Sha2_512,
+                32,
+                false,
+            )),
+            Self::RsaPss4096Sha512Mgf1SaltLen64WithParams => Some(mgf1(
+                4096,
+                HashFunction::Sha2_512,
+                HashFunction::Sha2_512,
+                64,
+                true,
+            )),
+            Self::RsaPss4096Sha512Mgf1SaltLen64 => Some(mgf1(
+                4096,
+                HashFunction::Sha2_512,
+                HashFunction::Sha2_512,
+                64,
+                false,
+            )),
+            Self::RsaPss4096Shake256 => Some(shake(4096, Mgf::Shake256)),
+            Self::RsaPssmiscWithParams => None,
+            Self::RsaPssmisc => None,
+        }
+    }
+}
+
 define_algorithm_map!("RSASSA-PSS" => RsaPss);
 
 define_test_flags!(
@@ -77,7 +260,7 @@ define_test_group!(
     "publicKey" => key: RsaPublic,
     "publicKeyAsn" => asn_key: ByteString,
     "publicKeyDer" => der: ByteString,
-    "publicKeyPem" => pem: String,
+    "publicKeyPem" => pem: Pem,
     "publicKeyJwk" => jwk: Option<RsaPublicJwk>,
     "keySize" => key_size: usize,
     mgf: Mgf,
@@ -87,3 +270,227 @@ define_test_group!(
 );
 
 define_test!(msg: ByteString, sig: ByteString);
+
+impl TestGroup {
+    /// Return the parameters of this group
+    pub fn params(&self) -> RsaParams {
+        RsaParams {
+            modulus_bits: self.key_size,
+            hash: self.hash,
+            mgf: self.mgf,
+            mgf_hash: self.mgf_hash,
+            salt_len: Some(self.salt_size),
+            with_params: self.test_type == TestGroupTypeId::RsaPssVerifyWithParam,
+        }
+    }
+
+    /// Return the JWK `alg` parameter for this group, if JOSE defines one
+    pub fn jwk_alg(&self) -> Option<&'static str> {
+        if self.mgf != Mgf::Mgf1
+            || self.mgf_hash != Some(self.hash)
+            || self.salt_size != self.hash.output_len()
+        {
+            return None;
+        }
+        match self.hash {
+            HashFunction::Sha2_256 => Some("PS256"),
+            HashFunction::Sha2_384 => Some("PS384"),
+            HashFunction::Sha2_512 => Some("PS512"),
+            _ => None,
+        }
+    }
+}
+
+pub use crate::der::RSASSA_PSS_OID;
+
+const TAG_HASH_ALGORITHM: u8 = 0xa0;
+const TAG_MASK_GEN_ALGORITHM: u8 = 0xa1;
+const TAG_SALT_LENGTH: u8 = 0xa2;
+const TAG_TRAILER_FIELD: u8 = 0xa3;
+
+/// Errors which can occur when decoding RSASSA-PSS parameters
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum PssParamsError {
+    /// The DER encoding was invalid
+    Der(DerError),
+    /// The key algorithm was neither rsaEncryption nor id-RSASSA-PSS
+    UnexpectedAlgorithm,
+    /// A hash function was not recognized
+    UnknownHash,
+    /// A hash function had parameters other than NULL
+    InvalidHashParameters,
+    /// The mask generation function was not MGF1
+    UnknownMgf,
+    /// The salt length is too large to be represented
+    InvalidSaltLength,
+    /// The trailer field was not trailerFieldBC
+    InvalidTrailer,
+    /// A field was encoded with its DEFAULT value, rather than omitted as
+    /// DER requires
+    DefaultValueEncoded,
+}
+
+impl std::fmt::Display for PssParamsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Der(e) => write!(f, "Invalid DER RSASSA-PSS parameters: {}", e),
+            Self::UnexpectedAlgorithm => write!(f, "Unexpected RSA key algorithm"),
+            Self::UnknownHash => write!(f, "Unknown RSASSA-PSS hash function"),
+            Self::InvalidHashParameters => write!(f, "Invalid RSASSA-PSS hash parameters"),
+            Self::UnknownMgf => write!(f, "Unknown RSASSA-PSS mask generation function"),
+            Self::InvalidSaltLength => write!(f, "Invalid RSASSA-PSS salt length"),
+            Self::InvalidTrailer => write!(f, "Invalid RSASSA-PSS trailer field"),
+            Self::DefaultValueEncoded => write!(f, "RSASSA-PSS parameter encodes its default"),
+        }
+    }
+}
+
+impl std::error::Error for PssParamsError {}
+
+impl From<DerError> for PssParamsError {
+    fn from(e: DerError) -> Self {
+        Self::Der(e)
+    }
+}
+
+/// The RSASSA-PSS-params of a key, as defined in RFC 4055
+///
+/// The trailer field is not included, since trailerFieldBC is the only
+/// value allowed.
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub struct PssParams {
+    /// The hash function applied to the message
+    pub hash: HashFunction,
+    /// The hash function used by MGF1
+    pub mgf_hash: HashFunction,
+    /// The salt length in bytes
+    pub salt_len: usize,
+}
+
+impl Default for PssParams {
+    fn default() -> Self {
+        Self {
+            hash: HashFunction::Sha1,
+            mgf_hash: HashFunction::Sha1,
+            salt_len: 20,
+        }
+    }
+}
+
+// Decode a hash AlgorithmIdentifier, whose parameters are NULL or absent
+fn read_hash_algorithm(bytes: &[u8]) -> Result<HashFunction, PssParamsError> {
+    let mut outer = Reader::new(bytes);
+    let mut alg_id = Reader::new(outer.read(TAG_SEQUENCE)?);
+    outer.finish()?;
+
+    let hash = HashFunction::from_oid(&alg_id.read_oid()?).ok_or(PssParamsError::UnknownHash)?;
+    if !alg_id.is_empty() && alg_id.read_raw()? != [TAG_NULL, 0] {
+        return Err(PssParamsError::InvalidHashParameters);
+    }
+    alg_id.finish()?;
+    Ok(hash)
+}
+
+// Decode a non-negative INTEGER wrapped in an explicit tag
+fn read_tagged_integer(bytes: &[u8]) -> Result<&[u8], DerError> {
+    let mut field = Reader::new(bytes);
+    let value = field.read_unsigned_integer()?;
+    field.finish()?;
+    Ok(value)
+}
+
+impl PssParams {
+    /// Decode a DER encoded RSASSA-PSS-params SEQUENCE
+    ///
+    /// Omitted fields take their default values. Fields which are present
+    /// but equal to their default are rejected, as DER requires them to be
+    /// omitted.
+    pub fn from_der(bytes: &[u8]) -> Result<Self, PssParamsError> {
+        let mut outer = Reader::new(bytes);
+        let mut seq = Reader::new(outer.read(TAG_SEQUENCE)?);
+        outer.finish()?;
+
+        let mut params = Self::default();
+
+        if seq.peek_tag() == Some(TAG_HASH_ALGORITHM) {
+            params.hash = read_hash_algorithm(seq.read(TAG_HASH_ALGORITHM)?)?;
+            if params.hash == HashFunction::Sha1 {
+                return Err(PssParamsError::DefaultValueEncoded);
+            }
+        }
+
+        if seq.peek_tag() == Some(TAG_MASK_GEN_ALGORITHM) {
+            let mut field = Reader::new(seq.read(TAG_MASK_GEN_ALGORITHM)?);
+            let mut alg_id = Reader::new(field.read(TAG_SEQUENCE)?);
+            field.finish()?;
+
+            if alg_id.read_oid()? != Mgf::Mgf1.oid() {
+                return Err(PssParamsError::UnknownMgf);
+            }
+            params.mgf_hash = read_hash_algorithm(alg_id.rest())?;
+            if params.mgf_hash == HashFunction::Sha1 {
+                return Err(PssParamsError::DefaultValueEncoded);
+            }
+        }
+
+        if seq.peek_tag() == Some(TAG_SALT_LENGTH) {
+            let value = read_tagged_integer(seq.read(TAG_SALT_LENGTH)?)?;
+            if value.len() > std::mem::size_of::<usize>() {
+                return Err(PssParamsError::InvalidSaltLength);
+            }
+            params.salt_len = value.iter().fold(0, |acc, &b| (acc << 8) | b as usize);
+            if params.salt_len == 20 {
+                return Err(PssParamsError::DefaultValueEncoded);
+            }
+        }
+
+        if seq.peek_tag() == Some(TAG_TRAILER_FIELD) {
+            // trailerFieldBC (1) is both the default and the only valid value
+            return Err(match read_tagged_integer(seq.read(TAG_TRAILER_FIELD)?)? {
+                [1] => PssParamsError::DefaultValueEncoded,
+                _ => PssParamsError::InvalidTrailer,
+            });
+        }
+
+        seq.finish()?;
+        Ok(params)
+    }
+
+    /// Decode the RSASSA-PSS-params of a DER encoded SubjectPublicKeyInfo
+    ///
+    /// Returns `None` for an rsaEncryption key, or an id-RSASSA-PSS key
+    /// without parameters, since neither restricts the parameters used.
+    pub fn from_spki_der(spki: &[u8]) -> Result<Option<Self>, PssParamsError> {
+        let spki = SubjectPublicKeyInfo::from_der(spki)?;
+        let algorithm = spki.algorithm;
+
+        match algorithm.oid.as_str() {
+            RSA_ENCRYPTION_OID if algorithm.has_null_parameters() => Ok(None),
+            RSASSA_PSS_OID => algorithm.parameters.map(Self::from_der).transpose(),
+            _ => Err(PssParamsError::UnexpectedAlgorithm),
+        }
+    }
+
+    /// Return true if these parameters are those of the test group
+    pub fn matches(&self, group: &TestGroup) -> bool {
+        group.mgf == Mgf::Mgf1
+            && group.hash == self.hash
+            && group.mgf_hash == Some(self.mgf_hash)
+            && group.salt_size == self.salt_len
+    }
+}
+
+impl TestGroup {
+    /// Decode the RSASSA-PSS-params embedded in the public key, if any
+    pub fn pss_params(&self) -> Result<Option<PssParams>, PssParamsError> {
+        PssParams::from_spki_der(&self.der)
+    }
+}
+
+#[cfg(feature = "digest")]
+impl Test {
+    /// Return the digest of the message, using the hash function of the group
+    pub fn digest(&self, group: &TestGroup) -> Vec<u8> {
+        group.hash.digest(&self.msg)
+    }
+}
@@ -0,0 +1,122 @@
+//! ECDSA and DSA signature encodings
+//!
+//! The ECDSA and DSA tests come in two flavors, with signatures either DER
+//! encoded as `SEQUENCE { r INTEGER, s INTEGER }` or in the IEEE P1363
+//! format, which is the fixed length concatenation of r and s. This module
+//! converts between the two.
+
+use super::*;
+use crate::der::{DerError, Reader};
+
+/// Errors which can occur when decoding or encoding a signature
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum SignatureError {
+    /// The DER encoding was invalid
+    Der(DerError),
+    /// A P1363 signature did not have the expected length
+    InvalidLength { expected: usize, actual: usize },
+    /// r or s was too large to be encoded at the requested width
+    IntegerTooLarge,
+}
+
+impl std::fmt::Display for SignatureError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Der(e) => write!(f, "Invalid DER signature: {}", e),
+            Self::InvalidLength { expected, actual } => write!(
+                f,
+                "Invalid P1363 signature length {} (expected {})",
+                actual, expected
+            ),
+            Self::IntegerTooLarge => write!(f, "Signature integer exceeds encoding width"),
+        }
+    }
+}
+
+impl std::error::Error for SignatureError {}
+
+impl From<DerError> for SignatureError {
+    fn from(e: DerError) -> Self {
+        Self::Der(e)
+    }
+}
+
+/// An ECDSA or DSA signature
+///
+/// The values of r and s are kept in minimal big-endian form, so signatures
+/// decoded from either format compare equal.
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct Signature {
+    pub r: LargeInteger,
+    pub s: LargeInteger,
+}
+
+impl Signature {
+    /// Decode a DER encoded signature
+    ///
+    /// Any deviation from DER, including BER encodings which are otherwise
+    /// valid, is rejected with the specific defect.
+    pub fn from_der(bytes: &[u8]) -> Result<Self, SignatureError> {
+        let mut outer = Reader::new(bytes);
+        let mut seq = Reader::new(outer.read(der::TAG_SEQUENCE)?);
+        outer.finish()?;
+
+        let r = seq.read_unsigned_integer()?;
+        let s = seq.read_unsigned_integer()?;
+        seq.finish()?;
+
+        Ok(Self {
+            r: LargeInteger::new(r.to_vec()),
+            s: LargeInteger::new(s.to_vec()),
+        })
+    }
+
+    /// Return the DER encoding of the signature
+    pub fn to_der(&self) -> Vec<u8> {
+        der::encode_sequence(&[
+            &der::encode_unsigned_integer(&self.r),
+            &der::encode_unsigned_integer(&self.s),
+        ])
+    }
+
+    /// Decode a P1363 encoded signature where r and s are each `width` bytes
+    pub fn from_p1363(bytes: &[u8], width: usize) -> Result<Self, SignatureError> {
+        if bytes.len() != 2 * width {
+            return Err(SignatureError::InvalidLength {
+                expected: 2 * width,
+                actual: bytes.len(),
+            });
+        }
+
+        let trim = |v: &[u8]| v.iter().copied().skip_while(|&b| b == 0).collect();
+
+        Ok(Self {
+            r: LargeInteger::new(trim(&bytes[..width])),
+            s: LargeInteger::new(trim(&bytes[width..])),
+        })
+    }
+
+    /// Return the P1363 encoding of the signature with r and s each `width` bytes
+    pub fn to_p1363(&self, width: usize) -> Result<Vec<u8>, SignatureError> {
+        let mut out = self
+            .r
+            .to_be_bytes_padded(width)
+            .ok_or(SignatureError::IntegerTooLarge)?;
+        out.extend(
+            self.s
+                .to_be_bytes_padded(width)
+                .ok_or(SignatureError::IntegerTooLarge)?,
+        );
+        Ok(out)
+    }
+}
+
+/// Convert a DER encoded signature to P1363 format
+pub fn der_to_p1363(sig: &[u8], width: usize) -> Result<Vec<u8>, SignatureError> {
+    Signature::from_der(sig)?.to_p1363(width)
+}
+
+/// Convert a P1363 encoded signature to DER format
+pub fn p1363_to_der(sig: &[u8], width: usize) -> Result<Vec<u8>, SignatureError> {
+    Ok(Signature::from_p1363(sig, width)?.to_der())
+}
@@ -9,6 +9,76 @@ fn int_from_base64<'de, D: Deserializer<'de>>(deserializer: D) -> Result<LargeIn
     Ok(LargeInteger::new(bytes))
 }
 
+/// Errors found when checking a JWK against the other encodings of its key
+#[cfg(any(
+    feature = "ecdsa",
+    feature = "eddsa",
+    feature = "rsa_enc",
+    feature = "rsa_sig"
+))]
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum JwkError {
+    /// The `kty` parameter is not the one used for this type of key
+    WrongKeyType,
+    /// The `kid` parameter is empty
+    MissingKeyId,
+    /// The `alg` parameter is not the algorithm of the test group
+    WrongAlgorithm,
+    /// The JWK holds a different key than the test group
+    KeyMismatch,
+}
+
+#[cfg(any(
+    feature = "ecdsa",
+    feature = "eddsa",
+    feature = "rsa_enc",
+    feature = "rsa_sig"
+))]
+impl std::fmt::Display for JwkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::WrongKeyType => write!(f, "Unexpected JWK key type"),
+            Self::MissingKeyId => write!(f, "JWK key id is empty"),
+            Self::WrongAlgorithm => write!(f, "Unexpected JWK algorithm"),
+            Self::KeyMismatch => write!(f, "JWK does not match the key"),
+        }
+    }
+}
+
+#[cfg(any(
+    feature = "ecdsa",
+    feature = "eddsa",
+    feature = "rsa_enc",
+    feature = "rsa_sig"
+))]
+impl std::error::Error for JwkError {}
+
+#[cfg(any(
+    feature = "ecdsa",
+    feature = "eddsa",
+    feature = "rsa_enc",
+    feature = "rsa_sig"
+))]
+fn check_jwk_header(kty: &str, expected_kty: &str, kid: &str) -> Result<(), JwkError> {
+    if kty != expected_kty {
+        return Err(JwkError::WrongKeyType);
+    }
+    if kid.is_empty() {
+        return Err(JwkError::MissingKeyId);
+    }
+    Ok(())
+}
+
+#[cfg(any(
+    feature = "ecdsa",
+    feature = "eddsa",
+    feature = "rsa_enc",
+    feature = "rsa_sig"
+))]
+fn base64url(value: &[u8]) -> String {
+    data_encoding::BASE64URL_NOPAD.encode(value)
+}
+
 #[cfg(feature = "ecdsa")]
 #[derive(Debug, Clone, Hash, Eq, PartialEq, serde_derive::Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -23,6 +93,80 @@ pub struct EcdsaPublicJwk {
     pub affine_y: LargeInteger,
 }
 
+#[cfg(feature = "ecdsa")]
+impl EcdsaPublicJwk {
+    /// Return the public point
+    pub fn point(&self) -> Result<ec_point::EcPoint, ec_point::PointError> {
+        ec_point::EcPoint::new(self.curve, &self.affine_x, &self.affine_y)
+    }
+
+    /// Return the key as an `EcdsaPublic`, encoded as in the Wycheproof data
+    pub fn to_typed(&self) -> EcdsaPublic {
+        let key = [&[0x04], self.affine_x.as_slice(), self.affine_y.as_slice()].concat();
+        EcdsaPublic {
+            curve: self.curve,
+            key_size: self.curve.field_bits(),
+            typ: EcPublicKeyTypeId {},
+            key: ByteString { value: key },
+            affine_x: LargeInteger::from_unsigned(&self.affine_x),
+            affine_y: LargeInteger::from_unsigned(&self.affine_y),
+        }
+    }
+
+    /// Check the `kty` and `kid` parameters, and that the JWK holds `key`
+    pub fn check(&self, key: &EcdsaPublic) -> Result<(), JwkError> {
+        check_jwk_header(&self.kty, "EC", &self.kid)?;
+        if self.to_typed() != *key {
+            return Err(JwkError::KeyMismatch);
+        }
+        Ok(())
+    }
+
+    /// Return the JSON which is hashed to compute the RFC 7638 thumbprint
+    pub fn thumbprint_input(&self) -> String {
+        format!(
+            r#"{{"crv":"{}","kty":"{}","x":"{}","y":"{}"}}"#,
+            self.curve.jwk_name().unwrap_or_else(|| self.curve.name()),
+            self.kty,
+            base64url(&self.affine_x),
+            base64url(&self.affine_y)
+        )
+    }
+
+    /// Return the RFC 7638 thumbprint of the key
+    #[cfg(feature = "digest")]
+    pub fn thumbprint(&self, hash: HashFunction) -> Vec<u8> {
+        hash.digest(self.thumbprint_input().as_bytes())
+    }
+}
+
+/// An elliptic curve key as used by the ECDH WebCrypto tests
+///
+/// Public keys omit the private scalar `d`.
+#[cfg(feature = "ecdh")]
+#[derive(Debug, Clone, Hash, Eq, PartialEq, serde_derive::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EcdhJwk {
+    #[serde(rename = "crv")]
+    pub curve: EllipticCurve,
+    pub kid: String,
+    pub kty: String,
+    #[serde(deserialize_with = "int_from_base64", rename = "x")]
+    pub affine_x: LargeInteger,
+    #[serde(deserialize_with = "int_from_base64", rename = "y")]
+    pub affine_y: LargeInteger,
+    #[serde(default, deserialize_with = "opt_int_from_base64")]
+    pub d: Option<LargeInteger>,
+}
+
+#[cfg(feature = "ecdh")]
+impl EcdhJwk {
+    /// Return the public point
+    pub fn point(&self) -> Result<ec_point::EcPoint, ec_point::PointError> {
+        ec_point::EcPoint::new(self.curve, &self.affine_x, &self.affine_y)
+    }
+}
+
 #[cfg(feature = "rsa_sig")]
 #[derive(Debug, Clone, Hash, Eq, PartialEq, serde_derive::Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -36,7 +180,7 @@ pub struct RsaPublicJwk {
     pub n: LargeInteger,
 }
 
-#[cfg(feature = "rsa_enc")]
+#[cfg(any(feature = "rsa_sig", feature = "rsa_enc"))]
 #[derive(Debug, Clone, Hash, Eq, PartialEq, serde_derive::Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct RsaPrivateJwk {
@@ -73,6 +217,41 @@ pub struct EddsaPublicJwk {
     pub x: LargeInteger,
 }
 
+/// An octet key pair JWK as used by the XDH tests (RFC 8037)
+///
+/// Every parameter is optional, since some tests deliberately use keys
+/// with missing or mismatched parameters.
+#[cfg(feature = "xdh")]
+#[derive(Debug, Clone, Hash, Eq, PartialEq, serde_derive::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct XdhJwk {
+    pub kty: Option<String>,
+    #[serde(rename = "crv")]
+    pub curve: Option<String>,
+    pub kid: Option<String>,
+    #[serde(default, deserialize_with = "opt_bytes_from_base64")]
+    pub x: Option<ByteString>,
+    #[serde(default, deserialize_with = "opt_bytes_from_base64")]
+    pub y: Option<ByteString>,
+    #[serde(default, deserialize_with = "opt_bytes_from_base64")]
+    pub d: Option<ByteString>,
+}
+
+#[cfg(feature = "xdh")]
+impl XdhJwk {
+    /// Return the curve, or `None` unless this is an OKP key for X25519 or X448
+    pub fn montgomery_curve(&self) -> Option<MontgomeryCurve> {
+        if self.kty.as_deref() != Some("OKP") {
+            return None;
+        }
+        match self.curve.as_deref() {
+            Some("X25519") => Some(MontgomeryCurve::X25519),
+            Some("X448") => Some(MontgomeryCurve::X448),
+            _ => None,
+        }
+    }
+}
+
 #[cfg(feature = "rsa_enc")]
 #[derive(Debug, Clone, Hash, Eq, PartialEq, serde_derive::Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -93,9 +272,23 @@ pub struct RsaPrivate {
     pub d2: LargeInteger,
     #[serde(rename = "coefficient")]
     pub c: LargeInteger,
+    #[serde(rename = "otherPrimeInfos", default)]
+    pub other_primes: Vec<RsaOtherPrimeInfo>,
 }
 
-#[cfg(feature = "rsa_sig")]
+/// An additional prime of a multi-prime RSA key (RFC 8017 section 3.2)
+#[cfg(feature = "rsa_enc")]
+#[derive(Debug, Clone, Hash, Eq, PartialEq, serde_derive::Deserialize)]
+pub struct RsaOtherPrimeInfo {
+    /// The prime
+    pub r: LargeInteger,
+    /// The CRT exponent d mod (r - 1)
+    pub d: LargeInteger,
+    /// The CRT coefficient, the inverse of the product of the preceding primes mod r
+    pub t: LargeInteger,
+}
+
+#[cfg(any(feature = "rsa_sig", feature = "rsa_enc"))]
 #[derive(Debug, Clone, Hash, Eq, PartialEq, serde_derive::Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct RsaPublic {
@@ -105,6 +298,426 @@ pub struct RsaPublic {
     pub n: LargeInteger,
 }
 
+/// An RSA private key given only by its modulus and exponents, without
+/// the primes and CRT parameters
+#[cfg(feature = "rsa_sig")]
+#[derive(Debug, Clone, Hash, Eq, PartialEq, serde_derive::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RsaPrivateNoCrt {
+    #[serde(rename = "publicExponent")]
+    pub e: LargeInteger,
+    #[serde(rename = "privateExponent")]
+    pub d: LargeInteger,
+    #[serde(rename = "modulus")]
+    pub n: LargeInteger,
+}
+
+#[cfg(feature = "rsa_sig")]
+impl RsaPrivateNoCrt {
+    /// Return the public key
+    pub fn public(&self) -> RsaPublic {
+        RsaPublic {
+            e: self.e.clone(),
+            n: self.n.clone(),
+        }
+    }
+}
+
+#[cfg(any(feature = "rsa_sig", feature = "rsa_enc"))]
+impl RsaPublic {
+    // The RFC 7638 thumbprint input of the key as a JWK
+    fn jwk_thumbprint_input(&self) -> String {
+        format!(
+            r#"{{"e":"{}","kty":"RSA","n":"{}"}}"#,
+            base64url(self.e.trimmed()),
+            base64url(self.n.trimmed())
+        )
+    }
+
+    /// Return the PKCS #1 `RSAPublicKey` DER encoding of the key
+    pub fn to_pkcs1_der(&self) -> Vec<u8> {
+        der::encode_sequence(&[
+            &der::encode_unsigned_integer(&self.n),
+            &der::encode_unsigned_integer(&self.e),
+        ])
+    }
+
+    /// Return true if the `SubjectPublicKeyInfo` holds this key
+    ///
+    /// Both rsaEncryption and id-RSASSA-PSS keys are accepted. Any
+    /// RSASSA-PSS parameters are not checked.
+    pub fn matches_spki(&self, spki: &der::SubjectPublicKeyInfo) -> bool {
+        let algorithm = &spki.algorithm;
+        let valid_algorithm = match algorithm.oid.as_str() {
+            der::RSA_ENCRYPTION_OID => algorithm.has_null_parameters(),
+            der::RSASSA_PSS_OID => true,
+            _ => false,
+        };
+        valid_algorithm && spki.public_key == self.to_pkcs1_der()
+    }
+}
+
+/// Errors which can occur when decoding or checking an RSA private key
+#[cfg(feature = "rsa_enc")]
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum RsaKeyError {
+    /// The DER encoding was invalid
+    Der(der::DerError),
+    /// The key version was not 0 (two-prime) or 1 (multi-prime), or did
+    /// not match the number of primes
+    UnsupportedVersion,
+    /// The PKCS #8 algorithm was not rsaEncryption with NULL parameters
+    UnexpectedAlgorithm,
+    /// The modulus is not the product of the primes
+    ModulusMismatch,
+    /// The CRT values of one of the additional primes are not consistent
+    InvalidOtherPrimeInfo,
+    /// The private exponent is not the inverse of the public exponent
+    InvalidPrivateExponent,
+    /// The first CRT exponent is not d mod (p - 1)
+    InvalidExponent1,
+    /// The second CRT exponent is not d mod (q - 1)
+    InvalidExponent2,
+    /// The CRT coefficient is not the inverse of q mod p
+    InvalidCoefficient,
+}
+
+#[cfg(feature = "rsa_enc")]
+impl std::fmt::Display for RsaKeyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Der(e) => write!(f, "Invalid DER RSA key: {}", e),
+            Self::UnsupportedVersion => write!(f, "Unsupported RSA private key version"),
+            Self::UnexpectedAlgorithm => write!(f, "Unexpected PKCS #8 key algorithm"),
+            Self::ModulusMismatch => write!(f, "RSA modulus is not the product of the primes"),
+            Self::InvalidOtherPrimeInfo => write!(f, "Invalid RSA other prime info"),
+            Self::InvalidPrivateExponent => write!(f, "Invalid RSA private exponent"),
+            Self::InvalidExponent1 => write!(f, "Invalid RSA CRT exponent d mod (p - 1)"),
+            Self::InvalidExponent2 => write!(f, "Invalid RSA CRT exponent d mod (q - 1)"),
+            Self::InvalidCoefficient => write!(f, "Invalid RSA CRT coefficient"),
+        }
+    }
+}
+
+#[cfg(feature = "rsa_enc")]
+impl std::error::Error for RsaKeyError {}
+
+#[cfg(feature = "rsa_enc")]
+impl From<der::DerError> for RsaKeyError {
+    fn from(e: der::DerError) -> Self {
+        Self::Der(e)
+    }
+}
+
+// Compare two integers ignoring any leading zero bytes
+#[cfg(feature = "rsa_enc")]
+fn same_integer(a: &LargeInteger, b: &LargeInteger) -> bool {
+    let len = std::cmp::max(a.len(), b.len());
+    a.to_be_bytes_padded(len) == b.to_be_bytes_padded(len)
+}
+
+#[cfg(feature = "rsa_enc")]
+impl RsaPrivate {
+    /// Return the public key
+    pub fn public(&self) -> RsaPublic {
+        RsaPublic {
+            e: self.e.clone(),
+            n: self.n.clone(),
+        }
+    }
+
+    /// Return true if both keys have the same values, ignoring any
+    /// differences in leading zero bytes
+    pub fn is_same_key(&self, other: &RsaPrivate) -> bool {
+        same_integer(&self.e, &other.e)
+            && same_integer(&self.d, &other.d)
+            && same_integer(&self.n, &other.n)
+            && same_integer(&self.p, &other.p)
+            && same_integer(&self.q, &other.q)
+            && same_integer(&self.d1, &other.d1)
+            && same_integer(&self.d2, &other.d2)
+            && same_integer(&self.c, &other.c)
+            && self.other_primes.len() == other.other_primes.len()
+            && self
+                .other_primes
+                .iter()
+                .zip(&other.other_primes)
+                .all(|(a, b)| {
+                    same_integer(&a.r, &b.r) && same_integer(&a.d, &b.d) && same_integer(&a.t, &b.t)
+                })
+    }
+
+    /// Return the PKCS #1 `RSAPrivateKey` DER encoding of the key
+    ///
+    /// Multi-prime keys are encoded as version 1 with `otherPrimeInfos`.
+    pub fn to_pkcs1_der(&self) -> Vec<u8> {
+        let version: &[u8] = if self.other_primes.is_empty() {
+            &[]
+        } else {
+            &[1]
+        };
+
+        let mut elements = vec![
+            der::encode_unsigned_integer(version),
+            der::encode_unsigned_integer(&self.n),
+            der::encode_unsigned_integer(&self.e),
+            der::encode_unsigned_integer(&self.d),
+            der::encode_unsigned_integer(&self.p),
+            der::encode_unsigned_integer(&self.q),
+            der::encode_unsigned_integer(&self.d1),
+            der::encode_unsigned_integer(&self.d2),
+            der::encode_unsigned_integer(&self.c),
+        ];
+
+        if !self.other_primes.is_empty() {
+            let infos: Vec<Vec<u8>> = self
+                .other_primes
+                .iter()
+                .map(|info| {
+                    der::encode_sequence(&[
+                        &der::encode_unsigned_integer(&info.r),
+                        &der::encode_unsigned_integer(&info.d),
+                        &der::encode_unsigned_integer(&info.t),
+                    ])
+                })
+                .collect();
+            elements.push(der::encode(der::TAG_SEQUENCE, &infos.concat()));
+        }
+
+        der::encode(der::TAG_SEQUENCE, &elements.concat())
+    }
+
+    /// Decode a PKCS #1 `RSAPrivateKey`
+    ///
+    /// Both two-prime (version 0) and multi-prime (version 1) keys are supported.
+    pub fn from_pkcs1_der(bytes: &[u8]) -> Result<Self, RsaKeyError> {
+        fn int(reader: &mut der::Reader) -> Result<LargeInteger, der::DerError> {
+            Ok(LargeInteger::new(reader.read_unsigned_integer()?.to_vec()))
+        }
+
+        let mut outer = der::Reader::new(bytes);
+        let mut seq = der::Reader::new(outer.read(der::TAG_SEQUENCE)?);
+        outer.finish()?;
+
+        let multi_prime = match seq.read_unsigned_integer()? {
+            [] => false,
+            [1] => true,
+            _ => return Err(RsaKeyError::UnsupportedVersion),
+        };
+
+        let mut key = Self {
+            n: int(&mut seq)?,
+            e: int(&mut seq)?,
+            d: int(&mut seq)?,
+            p: int(&mut seq)?,
+            q: int(&mut seq)?,
+            d1: int(&mut seq)?,
+            d2: int(&mut seq)?,
+            c: int(&mut seq)?,
+            other_primes: Vec::new(),
+        };
+
+        if multi_prime {
+            let mut infos = der::Reader::new(seq.read(der::TAG_SEQUENCE)?);
+            while !infos.is_empty() {
+                let mut info = der::Reader::new(infos.read(der::TAG_SEQUENCE)?);
+                key.other_primes.push(RsaOtherPrimeInfo {
+                    r: int(&mut info)?,
+                    d: int(&mut info)?,
+                    t: int(&mut info)?,
+                });
+                info.finish()?;
+            }
+            if key.other_primes.is_empty() {
+                return Err(RsaKeyError::UnsupportedVersion);
+            }
+        }
+
+        seq.finish()?;
+        Ok(key)
+    }
+
+    /// Return the PKCS #8 `PrivateKeyInfo` DER encoding of the key
+    pub fn to_pkcs8_der(&self) -> Vec<u8> {
+        der::encode_sequence(&[
+            &der::encode_unsigned_integer(&[]),
+            &der::encode_sequence(&[
+                &der::encode_oid(der::RSA_ENCRYPTION_OID),
+                &der::encode(der::TAG_NULL, &[]),
+            ]),
+            &der::encode(der::TAG_OCTET_STRING, &self.to_pkcs1_der()),
+        ])
+    }
+
+    /// Decode a PKCS #8 `PrivateKeyInfo` containing an RSA key
+    pub fn from_pkcs8_der(bytes: &[u8]) -> Result<Self, RsaKeyError> {
+        let mut outer = der::Reader::new(bytes);
+        let mut seq = der::Reader::new(outer.read(der::TAG_SEQUENCE)?);
+        outer.finish()?;
+
+        if !seq.read_unsigned_integer()?.is_empty() {
+            return Err(RsaKeyError::UnsupportedVersion);
+        }
+
+        let alg_id = der::AlgorithmIdentifier::read(&mut seq)?;
+        if alg_id.oid != der::RSA_ENCRYPTION_OID || !alg_id.has_null_parameters() {
+            return Err(RsaKeyError::UnexpectedAlgorithm);
+        }
+
+        let key = Self::from_pkcs1_der(seq.read(der::TAG_OCTET_STRING)?)?;
+        seq.finish()?;
+        Ok(key)
+    }
+
+    /// Return true if the `PrivateKeyInfo` holds this key
+    pub fn matches_pkcs8(&self, info: &der::PrivateKeyInfo) -> bool {
+        info.algorithm.oid == der::RSA_ENCRYPTION_OID
+            && info.algorithm.has_null_parameters()
+            && matches!(Self::from_pkcs1_der(info.private_key), Ok(k) if k.is_same_key(self))
+    }
+
+    /// Check that the components of the key are consistent
+    ///
+    /// This checks that n is the product of the primes, that d is an inverse
+    /// of e modulo the lcm of each prime minus one, that the CRT exponents are
+    /// d reduced modulo each prime minus one, and that the coefficients are
+    /// the inverses of the product of the preceding primes. Primality of the
+    /// primes is not checked.
+    #[cfg(feature = "num-bigint")]
+    pub fn check(&self) -> Result<(), RsaKeyError> {
+        use num_bigint::BigUint;
+
+        let one = BigUint::from(1u32);
+        let e = self.e.as_num_bigint();
+        let d = self.d.as_num_bigint();
+        let p = self.p.as_num_bigint();
+        let q = self.q.as_num_bigint();
+
+        let mut product = &p * &q;
+        for info in &self.other_primes {
+            let r = info.r.as_num_bigint();
+            let r1 = &r - &one;
+
+            if (&e * &d) % &r1 != one
+                || info.d.as_num_bigint() != &d % &r1
+                || (info.t.as_num_bigint() * &product) % &r != one
+            {
+                return Err(RsaKeyError::InvalidOtherPrimeInfo);
+            }
+            product *= r;
+        }
+
+        if product != self.n.as_num_bigint() {
+            return Err(RsaKeyError::ModulusMismatch);
+        }
+
+        let p1 = &p - &one;
+        let q1 = &q - &one;
+
+        if (&e * &d) % &p1 != one || (&e * &d) % &q1 != one {
+            return Err(RsaKeyError::InvalidPrivateExponent);
+        }
+        if self.d1.as_num_bigint() != &d % &p1 {
+            return Err(RsaKeyError::InvalidExponent1);
+        }
+        if self.d2.as_num_bigint() != &d % &q1 {
+            return Err(RsaKeyError::InvalidExponent2);
+        }
+        if (self.c.as_num_bigint() * &q) % &p != one {
+            return Err(RsaKeyError::InvalidCoefficient);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "rsa_sig")]
+impl RsaPublicJwk {
+    /// Return the key as an `RsaPublic`, encoded as in the Wycheproof data
+    pub fn to_typed(&self) -> RsaPublic {
+        RsaPublic {
+            e: LargeInteger::from_unsigned(&self.e),
+            n: LargeInteger::from_unsigned(&self.n),
+        }
+    }
+
+    /// Check the `kty`, `kid` and `alg` parameters, and that the JWK holds `key`
+    pub fn check(&self, key: &RsaPublic, alg: &str) -> Result<(), JwkError> {
+        check_jwk_header(&self.kty, "RSA", &self.kid)?;
+        if self.alg != alg {
+            return Err(JwkError::WrongAlgorithm);
+        }
+        if self.to_typed() != *key {
+            return Err(JwkError::KeyMismatch);
+        }
+        Ok(())
+    }
+
+    /// Return the JSON which is hashed to compute the RFC 7638 thumbprint
+    pub fn thumbprint_input(&self) -> String {
+        self.to_typed().jwk_thumbprint_input()
+    }
+
+    /// Return the RFC 7638 thumbprint of the key
+    #[cfg(feature = "digest")]
+    pub fn thumbprint(&self, hash: HashFunction) -> Vec<u8> {
+        hash.digest(self.thumbprint_input().as_bytes())
+    }
+}
+
+#[cfg(any(feature = "rsa_sig", feature = "rsa_enc"))]
+impl RsaPrivateJwk {
+    /// Return the public key, encoded as in the Wycheproof data
+    pub fn public(&self) -> RsaPublic {
+        RsaPublic {
+            e: LargeInteger::from_unsigned(&self.e),
+            n: LargeInteger::from_unsigned(&self.n),
+        }
+    }
+
+    /// Return the key components as an `RsaPrivate`, encoded as in the
+    /// Wycheproof data
+    #[cfg(feature = "rsa_enc")]
+    pub fn to_private(&self) -> RsaPrivate {
+        RsaPrivate {
+            e: LargeInteger::from_unsigned(&self.e),
+            d: LargeInteger::from_unsigned(&self.d),
+            n: LargeInteger::from_unsigned(&self.n),
+            p: LargeInteger::from_unsigned(&self.p),
+            q: LargeInteger::from_unsigned(&self.q),
+            d1: LargeInteger::from_unsigned(&self.dp),
+            d2: LargeInteger::from_unsigned(&self.dq),
+            c: LargeInteger::from_unsigned(&self.qi),
+            other_primes: Vec::new(),
+        }
+    }
+
+    /// Check the `kty`, `kid` and `alg` parameters, and that the public
+    /// part of the JWK is `key`
+    pub fn check(&self, key: &RsaPublic, alg: &str) -> Result<(), JwkError> {
+        check_jwk_header(&self.kty, "RSA", &self.kid)?;
+        if self.alg != alg {
+            return Err(JwkError::WrongAlgorithm);
+        }
+        if self.public() != *key {
+            return Err(JwkError::KeyMismatch);
+        }
+        Ok(())
+    }
+
+    /// Return the JSON which is hashed to compute the RFC 7638 thumbprint
+    ///
+    /// Only the public parameters are included.
+    pub fn thumbprint_input(&self) -> String {
+        self.public().jwk_thumbprint_input()
+    }
+
+    /// Return the RFC 7638 thumbprint of the key
+    #[cfg(feature = "digest")]
+    pub fn thumbprint(&self, hash: HashFunction) -> Vec<u8> {
+        hash.digest(self.thumbprint_input().as_bytes())
+    }
+}
+
 define_typeid!(EcPublicKeyTypeId => "EcPublicKey");
 
 #[cfg(feature = "ecdsa")]
@@ -124,6 +737,28 @@ pub struct EcdsaPublic {
     pub affine_y: LargeInteger,
 }
 
+#[cfg(feature = "ecdsa")]
+impl EcdsaPublic {
+    /// Return the public point
+    pub fn point(&self) -> Result<ec_point::EcPoint, ec_point::PointError> {
+        ec_point::EcPoint::new(self.curve, &self.affine_x, &self.affine_y)
+    }
+
+    /// Return the SEC1 encoding of the public point in the requested format
+    pub fn to_sec1(
+        &self,
+        encoding: ec_point::PointEncoding,
+    ) -> Result<Vec<u8>, ec_point::PointError> {
+        self.point()?.to_sec1(self.curve, encoding)
+    }
+
+    /// Return true if the `SubjectPublicKeyInfo` holds this key, as an
+    /// uncompressed point on a named curve
+    pub fn matches_spki(&self, spki: &der::SubjectPublicKeyInfo) -> bool {
+        spki.ec_curve() == Some(self.curve) && spki.public_key == self.key.as_slice()
+    }
+}
+
 define_typeid!(DsaPublicKeyTypeId => "DsaPublicKey");
 
 #[cfg(feature = "dsa")]
@@ -153,3 +788,244 @@ pub struct EddsaPublic {
     #[serde(rename = "type")]
     typ: EddsaPublicKeyTypeId,
 }
+
+#[cfg(feature = "eddsa")]
+impl EddsaPublicJwk {
+    /// Return the key as an `EddsaPublic`
+    pub fn to_typed(&self) -> EddsaPublic {
+        EddsaPublic {
+            curve: self.curve,
+            key_size: self.curve.key_bits(),
+            pk: ByteString {
+                value: self.x.to_vec(),
+            },
+            typ: EddsaPublicKeyTypeId {},
+        }
+    }
+
+    /// Check the `kty` and `kid` parameters, and that the JWK holds `key`
+    pub fn check(&self, key: &EddsaPublic) -> Result<(), JwkError> {
+        check_jwk_header(&self.kty, "OKP", &self.kid)?;
+        if self.to_typed() != *key {
+            return Err(JwkError::KeyMismatch);
+        }
+        Ok(())
+    }
+
+    /// Return the JSON which is hashed to compute the RFC 7638 thumbprint
+    pub fn thumbprint_input(&self) -> String {
+        format!(
+            r#"{{"crv":"{}","kty":"{}","x":"{}"}}"#,
+            self.curve.jwk_name(),
+            self.kty,
+            base64url(&self.x)
+        )
+    }
+
+    /// Return the RFC 7638 thumbprint of the key
+    #[cfg(feature = "digest")]
+    pub fn thumbprint(&self, hash: HashFunction) -> Vec<u8> {
+        hash.digest(self.thumbprint_input().as_bytes())
+    }
+}
+
+/// The BLS12-381 group containing a point
+#[cfg(feature = "bls")]
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, serde_derive::Deserialize)]
+pub enum BlsGroup {
+    G1,
+    G2,
+}
+
+#[cfg(feature = "bls")]
+#[derive(Debug, Clone, Hash, Eq, PartialEq, serde_derive::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BlsPublic {
+    pub pk: ByteString,
+    pub group: BlsGroup,
+    #[serde(rename = "keySize")]
+    pub key_size: usize,
+}
+
+// Some of the JOSE test keys are not canonically encoded, so unlike
+// int_from_base64 this accepts non-zero trailing bits
+#[cfg(any(feature = "jose", feature = "ecdh", feature = "xdh"))]
+fn lenient_base64<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    let s: &str = Deserialize::deserialize(deserializer)?;
+    let mut spec = data_encoding::BASE64URL_NOPAD.specification();
+    spec.check_trailing_bits = false;
+    spec.encoding()
+        .map_err(D::Error::custom)?
+        .decode(s.as_bytes())
+        .map_err(D::Error::custom)
+}
+
+#[cfg(any(feature = "jose", feature = "ecdh"))]
+fn opt_int_from_base64<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<LargeInteger>, D::Error> {
+    Ok(Some(LargeInteger::new(lenient_base64(deserializer)?)))
+}
+
+#[cfg(any(feature = "jose", feature = "xdh"))]
+fn opt_bytes_from_base64<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<ByteString>, D::Error> {
+    Ok(Some(ByteString {
+        value: lenient_base64(deserializer)?,
+    }))
+}
+
+/// JSON Web Key types
+#[cfg(feature = "jose")]
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, serde_derive::Deserialize)]
+pub enum JwkKeyType {
+    #[serde(rename = "EC")]
+    Ec,
+    #[serde(rename = "RSA")]
+    Rsa,
+    #[serde(rename = "oct")]
+    Oct,
+}
+
+/// A JSON Web Key of any type (RFC 7517)
+///
+/// Every parameter other than the key type is optional, since some tests
+/// deliberately use keys with missing or mismatched parameters.
+#[cfg(feature = "jose")]
+#[derive(Debug, Clone, Hash, Eq, PartialEq, serde_derive::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Jwk {
+    pub kty: JwkKeyType,
+    pub kid: Option<String>,
+    pub alg: Option<String>,
+    #[serde(rename = "use")]
+    pub key_use: Option<String>,
+    pub key_ops: Option<Vec<String>>,
+    #[serde(rename = "crv")]
+    pub curve: Option<EllipticCurve>,
+    #[serde(default, deserialize_with = "opt_int_from_base64")]
+    pub x: Option<LargeInteger>,
+    #[serde(default, deserialize_with = "opt_int_from_base64")]
+    pub y: Option<LargeInteger>,
+    #[serde(default, deserialize_with = "opt_int_from_base64")]
+    pub d: Option<LargeInteger>,
+    #[serde(default, deserialize_with = "opt_int_from_base64")]
+    pub n: Option<LargeInteger>,
+    #[serde(default, deserialize_with = "opt_int_from_base64")]
+    pub e: Option<LargeInteger>,
+    #[serde(default, deserialize_with = "opt_int_from_base64")]
+    pub p: Option<LargeInteger>,
+    #[serde(default, deserialize_with = "opt_int_from_base64")]
+    pub q: Option<LargeInteger>,
+    #[serde(default, deserialize_with = "opt_int_from_base64")]
+    pub dp: Option<LargeInteger>,
+    #[serde(default, deserialize_with = "opt_int_from_base64")]
+    pub dq: Option<LargeInteger>,
+    #[serde(default, deserialize_with = "opt_int_from_base64")]
+    pub qi: Option<LargeInteger>,
+    #[serde(default, deserialize_with = "opt_bytes_from_base64")]
+    pub k: Option<ByteString>,
+}
+
+#[cfg(feature = "jose")]
+impl Jwk {
+    #[cfg(any(feature = "ecdsa", feature = "rsa_sig", feature = "rsa_enc"))]
+    fn new(kty: JwkKeyType, kid: &str, alg: Option<&str>) -> Self {
+        Self {
+            kty,
+            kid: Some(kid.to_string()),
+            alg: alg.map(|a| a.to_string()),
+            key_use: None,
+            key_ops: None,
+            curve: None,
+            x: None,
+            y: None,
+            d: None,
+            n: None,
+            e: None,
+            p: None,
+            q: None,
+            dp: None,
+            dq: None,
+            qi: None,
+            k: None,
+        }
+    }
+
+    /// Return true if this key contains private key material
+    pub fn is_private(&self) -> bool {
+        match self.kty {
+            JwkKeyType::Ec | JwkKeyType::Rsa => self.d.is_some(),
+            JwkKeyType::Oct => true,
+        }
+    }
+}
+
+#[cfg(all(feature = "jose", feature = "ecdsa"))]
+impl From<&EcdsaPublicJwk> for Jwk {
+    fn from(jwk: &EcdsaPublicJwk) -> Self {
+        Self {
+            curve: Some(jwk.curve),
+            x: Some(jwk.affine_x.clone()),
+            y: Some(jwk.affine_y.clone()),
+            ..Self::new(JwkKeyType::Ec, &jwk.kid, None)
+        }
+    }
+}
+
+#[cfg(all(feature = "jose", feature = "rsa_sig"))]
+impl From<&RsaPublicJwk> for Jwk {
+    fn from(jwk: &RsaPublicJwk) -> Self {
+        Self {
+            n: Some(jwk.n.clone()),
+            e: Some(jwk.e.clone()),
+            ..Self::new(JwkKeyType::Rsa, &jwk.kid, Some(&jwk.alg))
+        }
+    }
+}
+
+#[cfg(all(feature = "jose", any(feature = "rsa_sig", feature = "rsa_enc")))]
+impl From<&RsaPrivateJwk> for Jwk {
+    fn from(jwk: &RsaPrivateJwk) -> Self {
+        Self {
+            n: Some(jwk.n.clone()),
+            e: Some(jwk.e.clone()),
+            d: Some(jwk.d.clone()),
+            p: Some(jwk.p.clone()),
+            q: Some(jwk.q.clone()),
+            dp: Some(jwk.dp.clone()),
+            dq: Some(jwk.dq.clone()),
+            qi: Some(jwk.qi.clone()),
+            ..Self::new(JwkKeyType::Rsa, &jwk.kid, Some(&jwk.alg))
+        }
+    }
+}
+
+/// A JSON Web Key Set (RFC 7517 section 5)
+#[cfg(feature = "jose")]
+#[derive(Debug, Clone, Hash, Eq, PartialEq, serde_derive::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct JwkSet {
+    pub keys: Vec<Jwk>,
+}
+
+/// Either a single JSON Web Key or a JSON Web Key Set
+#[cfg(feature = "jose")]
+#[derive(Debug, Clone, Hash, Eq, PartialEq, serde_derive::Deserialize)]
+#[serde(untagged)]
+pub enum JwkOrSet {
+    Key(Box<Jwk>),
+    Set(JwkSet),
+}
+
+#[cfg(feature = "jose")]
+impl JwkOrSet {
+    /// Return the keys, which is a single key unless this is a key set
+    pub fn keys(&self) -> &[Jwk] {
+        match self {
+            Self::Key(key) => std::slice::from_ref(key.as_ref()),
+            Self::Set(set) => &set.keys,
+        }
+    }
+}
@@ -2,11 +2,23 @@
 
 use super::*;
 
-define_test_set!("xDH", "xdh_comp_schema_v1.json");
+define_test_set!(
+    "xDH",
+    "xdh_comp_schema_v1.json",
+    "xdh_asn_comp_schema_v1.json",
+    "xdh_jwk_comp_schema_v1.json",
+    "xdh_pem_comp_schema_v1.json"
+);
 
 define_test_set_names!(
     X25519 => "x25519",
     X448 => "x448",
+    X25519Asn => "x25519_asn",
+    X448Asn => "x448_asn",
+    X25519Jwk => "x25519_jwk",
+    X448Jwk => "x448_jwk",
+    X25519Pem => "x25519_pem",
+    X448Pem => "x448_pem",
 );
 
 define_algorithm_map!("XDH" => Xdh);
@@ -15,8 +27,10 @@ define_test_flags!(
     EdgeCaseMultiplication,
     EdgeCasePrivateKey,
     EdgeCaseShared,
+    InvalidPublic,
     "Ktv" => KnownTestVector,
     LowOrderPublic,
+    MissingOctetString,
     NonCanonicalPublic,
     Normal,
     PublicKeyTooLong,
@@ -28,12 +42,64 @@ define_test_flags!(
 
 define_test_group_type_id!(
     "XdhComp" => KeyAgreement,
+    "XdhAsnComp" => AsnKeyAgreement,
+    "XdhJwkComp" => JwkKeyAgreement,
+    "XdhPemComp" => PemKeyAgreement,
 );
 
 define_test_group!(curve: MontgomeryCurve);
 
+/// A public or private key, in the encoding given by the test group type
+#[derive(Debug, Clone, Hash, Eq, PartialEq, serde_derive::Deserialize)]
+#[serde(from = "EncodedKey<XdhJwk>")]
+pub enum XdhKey {
+    /// The raw key (`KeyAgreement`), or a DER encoded SubjectPublicKeyInfo
+    /// or PKCS #8 private key (`AsnKeyAgreement`)
+    Bytes(ByteString),
+    /// A JSON Web Key (`JwkKeyAgreement`)
+    Jwk(Box<XdhJwk>),
+    /// A PEM encoded key (`PemKeyAgreement`)
+    Pem(Pem),
+}
+
+impl From<EncodedKey<XdhJwk>> for XdhKey {
+    fn from(key: EncodedKey<XdhJwk>) -> Self {
+        match key {
+            EncodedKey::Bytes(b) => Self::Bytes(b),
+            EncodedKey::Jwk(jwk) => Self::Jwk(Box::new(jwk)),
+            EncodedKey::Pem(pem) => Self::Pem(pem),
+        }
+    }
+}
+
+impl XdhKey {
+    /// Return the key bytes, or `None` if the key is a JWK or PEM
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Self::Bytes(b) => Some(b),
+            _ => None,
+        }
+    }
+
+    /// Return the JWK, or `None` if the key is in another encoding
+    pub fn as_jwk(&self) -> Option<&XdhJwk> {
+        match self {
+            Self::Jwk(jwk) => Some(jwk),
+            _ => None,
+        }
+    }
+
+    /// Return the PEM encoding, or `None` if the key is in another encoding
+    pub fn as_pem(&self) -> Option<&Pem> {
+        match self {
+            Self::Pem(pem) => Some(pem),
+            _ => None,
+        }
+    }
+}
+
 define_test!(
-    "public" => public_key: ByteString,
-    "private" => private_key: ByteString,
+    "public" => public_key: XdhKey,
+    "private" => private_key: XdhKey,
     "shared" => shared_secret: ByteString,
 );
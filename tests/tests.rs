@@ -16,6 +16,47 @@ fn test_cipher_parsing() -> Result<(), wycheproof::WycheproofError> {
     Ok(())
 }
 
+#[cfg(feature = "daead")]
+#[test]
+fn test_daead_parsing() -> Result<(), wycheproof::WycheproofError> {
+    for test in wycheproof::daead::TestName::all() {
+        let _kat = wycheproof::daead::TestSet::load(test)?;
+    }
+    Ok(())
+}
+
+#[cfg(feature = "daead")]
+#[test]
+fn test_daead_rfc5297() -> Result<(), wycheproof::WycheproofError> {
+    use wycheproof::daead::{TestName, TestSet};
+
+    // RFC 5297 appendix A.1
+    let key = "fffefdfcfbfaf9f8f7f6f5f4f3f2f1f0f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff";
+    let aad = "101112131415161718191a1b1c1d1e1f2021222324252627";
+    let pt = "112233445566778899aabbccddee";
+    let siv = "85632d07c6e8f37f950acd320a2ecc93";
+    let ct = "40c02b9690c4dc04daef7f6afe5c";
+
+    let hex = |b: &[u8]| data_encoding::HEXLOWER.encode(b);
+
+    let kat = TestSet::load(TestName::AesSivCmac)?;
+    let rfc: Vec<_> = kat
+        .test_groups
+        .iter()
+        .flat_map(|g| g.tests.iter())
+        .filter(|t| t.comment == "RFC 5297")
+        .collect();
+    assert_eq!(rfc.len(), 1);
+
+    let t = rfc[0];
+    assert_eq!(t.result, wycheproof::TestResult::Valid);
+    assert_eq!(hex(&t.key), key);
+    assert_eq!(hex(&t.aad), aad);
+    assert_eq!(hex(&t.pt), pt);
+    assert_eq!(hex(&t.ct), format!("{}{}", siv, ct));
+    Ok(())
+}
+
 #[cfg(feature = "dsa")]
 #[test]
 fn test_dsa_parsing() -> Result<(), wycheproof::WycheproofError> {
@@ -79,6 +120,177 @@ fn test_fpe_list_parsing() -> Result<(), wycheproof::WycheproofError> {
     Ok(())
 }
 
+#[cfg(feature = "fpe")]
+#[test]
+fn test_fpe_numerals() -> Result<(), wycheproof::WycheproofError> {
+    use wycheproof::fpe::{NumeralError, Numerals};
+
+    for test in wycheproof::fpe_str::TestName::all() {
+        let kat = wycheproof::fpe_str::TestSet::load(test)?;
+        for group in kat.test_groups {
+            for t in &group.tests {
+                let pt = t.pt_numerals(&group);
+                if t.flags
+                    .contains(&wycheproof::fpe_str::TestFlag::InvalidPlaintext)
+                {
+                    assert!(matches!(pt, Err(NumeralError::NotInAlphabet(_))));
+                    continue;
+                }
+                let pt = pt.unwrap();
+                assert_eq!(pt.radix() as usize, group.radix);
+                assert_eq!(pt.to_alphabet(&group.alphabet).unwrap(), t.pt);
+            }
+        }
+    }
+
+    for test in wycheproof::fpe_list::TestName::all() {
+        let kat = wycheproof::fpe_list::TestSet::load(test)?;
+        for group in kat.test_groups {
+            for t in &group.tests {
+                let pt = t.pt_numerals(&group);
+                if t.flags
+                    .contains(&wycheproof::fpe_list::TestFlag::InvalidPlaintext)
+                {
+                    assert!(matches!(pt, Err(NumeralError::OutOfRange(_))));
+                } else {
+                    assert_eq!(pt.unwrap().len(), t.pt.len());
+                }
+            }
+        }
+    }
+
+    assert_eq!(
+        Numerals::from_list(1, &[0u32]),
+        Err(NumeralError::InvalidRadix(1))
+    );
+    assert_eq!(
+        Numerals::from_list(65537, &[0u32]),
+        Err(NumeralError::InvalidRadix(65537))
+    );
+    assert!(Numerals::from_list(65536, &[65535u32]).is_ok());
+    Ok(())
+}
+
+// An FF1 "implementation" which looks up the answers of the valid tests
+#[cfg(feature = "fpe")]
+type Ff1Input = (Vec<u8>, Vec<u8>, Vec<u32>);
+
+#[cfg(feature = "fpe")]
+#[derive(Default)]
+struct Ff1Oracle(std::collections::HashMap<Ff1Input, Vec<u32>>);
+
+#[cfg(feature = "fpe")]
+impl Ff1Oracle {
+    fn insert(&mut self, key: &[u8], tweak: &[u8], pt: &[u32], ct: &[u32]) {
+        self.0
+            .insert((key.to_vec(), tweak.to_vec(), pt.to_vec()), ct.to_vec());
+    }
+}
+
+#[cfg(feature = "fpe")]
+impl wycheproof::fpe::Ff1 for Ff1Oracle {
+    type Error = ();
+
+    fn ff1_encrypt(&self, key: &[u8], tweak: &[u8], _: u32, pt: &[u32]) -> Result<Vec<u32>, ()> {
+        let k = (key.to_vec(), tweak.to_vec(), pt.to_vec());
+        self.0.get(&k).cloned().ok_or(())
+    }
+}
+
+#[cfg(feature = "fpe")]
+#[test]
+fn test_fpe_ff1_runner() -> Result<(), wycheproof::WycheproofError> {
+    use wycheproof::fpe::{Ff1, Ff1Outcome};
+
+    struct Reject;
+
+    impl Ff1 for Reject {
+        type Error = ();
+
+        fn ff1_encrypt(&self, _: &[u8], _: &[u8], _: u32, _: &[u32]) -> Result<Vec<u32>, ()> {
+            Err(())
+        }
+    }
+
+    // Accepts any input, including numerals out of range for the radix
+    struct Accept;
+
+    impl Ff1 for Accept {
+        type Error = ();
+
+        fn ff1_encrypt(&self, _: &[u8], _: &[u8], _: u32, pt: &[u32]) -> Result<Vec<u32>, ()> {
+            Ok(pt.to_vec())
+        }
+    }
+
+    fn expected(must_fail: bool) -> Ff1Outcome {
+        if must_fail {
+            Ff1Outcome::Passed
+        } else {
+            Ff1Outcome::Failed
+        }
+    }
+
+    for test in wycheproof::fpe_str::TestName::all() {
+        let kat = wycheproof::fpe_str::TestSet::load(test)?;
+        let mut oracle = Ff1Oracle::default();
+        for group in &kat.test_groups {
+            assert_eq!(group.check_alphabet(), Ok(()));
+            for t in group.tests.iter().filter(|t| !t.result.must_fail()) {
+                let pt = t.pt_numerals(group).unwrap();
+                let ct = t.ct_numerals(group).unwrap();
+                oracle.insert(&t.key, &t.tweak, pt.digits(), ct.digits());
+            }
+        }
+        for group in &kat.test_groups {
+            for t in &group.tests {
+                let representable = t.pt.chars().all(|c| group.alphabet.contains(c));
+                if representable {
+                    assert_eq!(t.run_ff1(group, &oracle), Ff1Outcome::Passed);
+                    assert_eq!(t.run_ff1(group, &Reject), expected(t.result.must_fail()));
+                } else {
+                    assert!(t.result.must_fail());
+                    assert_eq!(t.run_ff1(group, &oracle), Ff1Outcome::Skipped);
+                    assert_eq!(t.run_ff1(group, &Accept), Ff1Outcome::Skipped);
+                }
+            }
+        }
+    }
+
+    let mut out_of_range = 0;
+    for test in wycheproof::fpe_list::TestName::all() {
+        let kat = wycheproof::fpe_list::TestSet::load(test)?;
+        let mut oracle = Ff1Oracle::default();
+        for group in &kat.test_groups {
+            for t in group.tests.iter().filter(|t| !t.result.must_fail()) {
+                let pt = t.pt_numerals(group).unwrap();
+                let ct = t.ct_numerals(group).unwrap();
+                oracle.insert(&t.key, &t.tweak, pt.digits(), ct.digits());
+            }
+        }
+        for group in &kat.test_groups {
+            for t in &group.tests {
+                let representable = t.pt.iter().all(|&v| v >= 0);
+                if representable {
+                    assert_eq!(t.run_ff1(group, &oracle), Ff1Outcome::Passed);
+                    assert_eq!(t.run_ff1(group, &Reject), expected(t.result.must_fail()));
+                    if t.result.must_fail() {
+                        // Numerals not less than the radix must reach the implementation
+                        assert_eq!(t.run_ff1(group, &Accept), Ff1Outcome::Failed);
+                        out_of_range += 1;
+                    }
+                } else {
+                    assert!(t.result.must_fail());
+                    assert_eq!(t.run_ff1(group, &oracle), Ff1Outcome::Skipped);
+                    assert_eq!(t.run_ff1(group, &Accept), Ff1Outcome::Skipped);
+                }
+            }
+        }
+    }
+    assert!(out_of_range > 0);
+    Ok(())
+}
+
 #[cfg(feature = "hkdf")]
 #[test]
 fn test_hkdf_parsing() -> Result<(), wycheproof::WycheproofError> {
@@ -142,6 +354,15 @@ fn test_rsa_pkcs1_decrypt_parsing() -> Result<(), wycheproof::WycheproofError> {
     Ok(())
 }
 
+#[cfg(feature = "rsa_sig")]
+#[test]
+fn test_rsa_pkcs1_sign_parsing() -> Result<(), wycheproof::WycheproofError> {
+    for test in wycheproof::rsa_pkcs1_sign::TestName::all() {
+        let _kat = wycheproof::rsa_pkcs1_sign::TestSet::load(test)?;
+    }
+    Ok(())
+}
+
 #[cfg(feature = "rsa_sig")]
 #[test]
 fn test_rsa_pkcs1_verify_parsing() -> Result<(), wycheproof::WycheproofError> {
@@ -169,6 +390,58 @@ fn test_xdh_parsing() -> Result<(), wycheproof::WycheproofError> {
     Ok(())
 }
 
+#[cfg(feature = "xdh")]
+#[test]
+fn test_xdh_key_encodings() -> Result<(), wycheproof::WycheproofError> {
+    use wycheproof::xdh::TestGroupTypeId;
+    use wycheproof::MontgomeryCurve;
+
+    for test in wycheproof::xdh::TestName::all() {
+        let kat = wycheproof::xdh::TestSet::load(test)?;
+        for group in kat.test_groups {
+            // The OID is 1.3.101.110 for X25519 and 1.3.101.111 for X448
+            let oid: &[u8] = match group.curve {
+                MontgomeryCurve::X25519 => &[0x06, 0x03, 0x2b, 0x65, 0x6e],
+                MontgomeryCurve::X448 => &[0x06, 0x03, 0x2b, 0x65, 0x6f],
+            };
+            for t in group.tests {
+                match group.test_type {
+                    TestGroupTypeId::KeyAgreement => {
+                        assert!(t.public_key.as_bytes().is_some());
+                        assert!(t.private_key.as_bytes().is_some());
+                    }
+                    TestGroupTypeId::AsnKeyAgreement => {
+                        let public = t.public_key.as_bytes().unwrap();
+                        let private = t.private_key.as_bytes().unwrap();
+                        assert_eq!(public[0], 0x30);
+                        if !t.result.must_fail() {
+                            assert!(public.windows(oid.len()).any(|w| w == oid));
+                            assert!(private.windows(oid.len()).any(|w| w == oid));
+                        }
+                    }
+                    TestGroupTypeId::JwkKeyAgreement => {
+                        let public = t.public_key.as_jwk().unwrap();
+                        let private = t.private_key.as_jwk().unwrap();
+                        assert_eq!(private.montgomery_curve(), Some(group.curve));
+                        assert!(private.d.is_some());
+                        if public.montgomery_curve().is_none() || public.x.is_none() {
+                            assert!(t.result.must_fail());
+                        }
+                    }
+                    TestGroupTypeId::PemKeyAgreement => {
+                        let public = t.public_key.as_pem().unwrap();
+                        let private = t.private_key.as_pem().unwrap();
+                        assert_eq!(public.label(), Ok("PUBLIC KEY"));
+                        assert_eq!(private.label(), Ok("PRIVATE KEY"));
+                        assert!(public.to_der().is_ok() && private.to_der().is_ok());
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
 #[cfg(feature = "mldsa_sign")]
 #[test]
 fn test_mldsa_sign_parsing() -> Result<(), wycheproof::WycheproofError> {
@@ -213,3 +486,1389 @@ fn test_pbkdf2_parsing() -> Result<(), wycheproof::WycheproofError> {
     }
     Ok(())
 }
+
+#[cfg(feature = "pbkdf2")]
+#[test]
+fn test_pbkdf2_iteration_budget() -> Result<(), wycheproof::WycheproofError> {
+    use wycheproof::pbkdf2::{TestFlag, TestName, TestSet};
+
+    let kat = TestSet::load(TestName::Pbkdf2HmacSha1)?;
+    let budget = kat.with_iteration_budget(4096);
+    assert!(budget
+        .within_budget
+        .iter()
+        .all(|t| t.iteration_count <= 4096));
+    assert_eq!(budget.retained.len(), 1);
+    assert!(budget.retained[0]
+        .flags
+        .contains(&TestFlag::LargeIterationCount));
+    assert!(budget.skipped.is_empty());
+
+    for test in TestName::all() {
+        let kat = TestSet::load(test)?;
+        let budget = kat.with_iteration_budget(0);
+        assert!(budget.within_budget.is_empty());
+        assert_eq!(
+            budget.tests().count() + budget.skipped.len(),
+            kat.number_of_tests
+        );
+        for flag in kat.notes.keys() {
+            assert!(budget.tests().any(|t| t.flags.contains(flag)));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "pbes2")]
+#[test]
+fn test_pbes2_iteration_budget() -> Result<(), wycheproof::WycheproofError> {
+    for test in wycheproof::pbes2::TestName::all() {
+        let kat = wycheproof::pbes2::TestSet::load(test)?;
+        let budget = kat.with_iteration_budget(100);
+        assert!(budget.within_budget.is_empty());
+        assert!(!budget.retained.is_empty());
+        for t in &budget.skipped {
+            assert!(t
+                .flags
+                .iter()
+                .all(|f| budget.tests().any(|r| r.flags.contains(f))));
+        }
+
+        let budget = kat.with_iteration_budget(4096);
+        assert_eq!(budget.within_budget.len(), kat.number_of_tests);
+    }
+    Ok(())
+}
+
+#[cfg(feature = "pbes2")]
+#[test]
+fn test_pbes2_params() -> Result<(), wycheproof::WycheproofError> {
+    use wycheproof::pbes2::{TestName, TestSet};
+    use wycheproof::HashFunction;
+
+    for test in TestName::all() {
+        let kat = TestSet::load(test)?;
+        let alg = kat.algorithm;
+        let name = format!("{:?}", alg);
+        let prf = alg.prf().name().trim_start_matches("SHA-");
+        assert!(name.contains(&format!("HmacSha{}And", prf)));
+        assert!(name.ends_with(&(8 * alg.cipher_key_len()).to_string()));
+        assert_eq!(alg.cipher_mode(), wycheproof::pbes2::CipherMode::Cbc);
+        assert_eq!(alg.prf() == HashFunction::Sha1, name.contains("Sha1And"));
+    }
+
+    let kat = TestSet::load(TestName::PbeWithHmacSha256AndAes128)?;
+    let t = &kat.test_groups[0].tests[0];
+    let expected = "304a302906092a864886f70d01050c301c0408fcd9a324f025ef4002021000300c06082a864886f70d02090500301d0609608648016503040102041042f02ff71b8524d1678ab2e34f9e7d47";
+    assert_eq!(
+        data_encoding::HEXLOWER.encode(&t.pbes2_params(kat.algorithm)),
+        expected
+    );
+    assert_eq!(
+        data_encoding::HEXLOWER.encode(&t.algorithm_identifier(kat.algorithm)),
+        format!("305706092a864886f70d01050d{}", expected)
+    );
+
+    // With the default PRF of HMAC-SHA-1 the PRF is omitted
+    let kat = TestSet::load(TestName::PbeWithHmacSha1AndAes128)?;
+    let t = &kat.test_groups[0].tests[0];
+    let params = t.pbes2_params(kat.algorithm);
+    let prf_oid = [0x06, 0x08, 0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x02, 0x07];
+    assert!(!params.windows(prf_oid.len()).any(|w| w == prf_oid));
+    Ok(())
+}
+
+#[cfg(feature = "ec")]
+#[test]
+fn test_ec_curve_params() {
+    fn bits(v: &[u8]) -> usize {
+        match v.iter().position(|&b| b != 0) {
+            Some(i) => (v.len() - i) * 8 - v[i].leading_zeros() as usize,
+            None => 0,
+        }
+    }
+
+    for curve in wycheproof::EllipticCurve::all() {
+        let params = curve.params();
+        assert_eq!(params.name, curve.name());
+        assert_eq!(params.oid, curve.oid());
+        assert_eq!(bits(&params.p), curve.field_bits());
+        assert_eq!(bits(&params.n), curve.order_bits());
+        assert_eq!(
+            wycheproof::EllipticCurve::from_oid(curve.oid()),
+            Some(curve)
+        );
+    }
+}
+
+#[cfg(feature = "ecdsa")]
+#[test]
+fn test_ecdsa_sec1_encoding() -> Result<(), wycheproof::WycheproofError> {
+    use wycheproof::ec_point::{PointEncoding, Sec1Point};
+
+    for test in wycheproof::ecdsa::TestName::all() {
+        let kat = wycheproof::ecdsa::TestSet::load(test)?;
+        for group in kat.test_groups {
+            let key = &group.key;
+            let uncompressed = key.to_sec1(PointEncoding::Uncompressed).unwrap();
+            assert_eq!(uncompressed, key.key.as_slice());
+
+            for encoding in [
+                PointEncoding::Compressed,
+                PointEncoding::Uncompressed,
+                PointEncoding::Hybrid,
+            ] {
+                let encoded = key.to_sec1(encoding).unwrap();
+                let decoded = Sec1Point::decode(key.curve, &encoded).unwrap();
+                assert_eq!(decoded.encoding(), Some(encoding));
+                if encoding != PointEncoding::Compressed {
+                    assert_eq!(decoded.point(), key.point().ok());
+                }
+            }
+
+            if let Some(jwk) = &group.jwk {
+                assert_eq!(
+                    jwk.point()
+                        .and_then(|p| p.to_sec1(jwk.curve, PointEncoding::Uncompressed)),
+                    Ok(uncompressed)
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "ecdh")]
+#[test]
+fn test_ecdh_binary_curves() -> Result<(), wycheproof::WycheproofError> {
+    use wycheproof::ecdh::{EcdhCurve, TestName};
+    use wycheproof::BinaryCurve;
+
+    let tests = [
+        (TestName::EcdhSect283k1, BinaryCurve::Sect283k1),
+        (TestName::EcdhSect283r1, BinaryCurve::Sect283r1),
+        (TestName::EcdhSect409k1, BinaryCurve::Sect409k1),
+        (TestName::EcdhSect409r1, BinaryCurve::Sect409r1),
+        (TestName::EcdhSect571k1, BinaryCurve::Sect571k1),
+        (TestName::EcdhSect571r1, BinaryCurve::Sect571r1),
+    ];
+    for (test, curve) in tests {
+        assert_eq!(BinaryCurve::from_oid(curve.oid()), Some(curve));
+        let kat = wycheproof::ecdh::TestSet::load(test)?;
+        for group in kat.test_groups {
+            assert_eq!(group.curve, EcdhCurve::Binary(curve));
+            assert_eq!(group.curve.prime(), None);
+            for t in group.tests {
+                // The scalar is at most one byte wider than the group order
+                let private = t.private_key.as_bytes().unwrap();
+                assert!(private.len() <= (curve.order_bits() + 7) / 8 + 1);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "ecdh")]
+#[test]
+fn test_ecdh_sec1_decoding() -> Result<(), wycheproof::WycheproofError> {
+    use wycheproof::ec_point::{PointError, Sec1Point};
+    use wycheproof::ecdh::{EcdhEncoding, TestFlag};
+
+    for test in wycheproof::ecdh::TestName::all() {
+        let kat = wycheproof::ecdh::TestSet::load(test)?;
+        for group in kat.test_groups {
+            if group.encoding != EcdhEncoding::EcPoint {
+                continue;
+            }
+            let curve = group.curve.prime().unwrap();
+            for t in group.tests {
+                let public = t.public_key.as_bytes().unwrap();
+                let decoded = Sec1Point::decode(curve, public);
+                if t.flags.contains(&TestFlag::InvalidEncoding) {
+                    assert!(decoded.is_err(), "{}", t.tc_id);
+                } else {
+                    assert!(decoded.is_ok());
+                }
+            }
+        }
+    }
+
+    for curve in wycheproof::EllipticCurve::all() {
+        assert_eq!(Sec1Point::decode(curve, &[]), Err(PointError::Empty));
+    }
+    Ok(())
+}
+
+#[cfg(feature = "ecdh")]
+#[test]
+fn test_ecdh_webcrypto_and_pem_keys() -> Result<(), wycheproof::WycheproofError> {
+    use wycheproof::ecdh::{EcdhEncoding, TestName};
+
+    let webcrypto = [
+        TestName::EcdhSecp256k1Webcrypto,
+        TestName::EcdhSecp256r1Webcrypto,
+        TestName::EcdhSecp384r1Webcrypto,
+        TestName::EcdhSecp521r1Webcrypto,
+    ];
+    for test in webcrypto {
+        let kat = wycheproof::ecdh::TestSet::load(test)?;
+        for group in kat.test_groups {
+            assert_eq!(group.encoding, EcdhEncoding::Webcrypto);
+            for t in group.tests {
+                let public = t.public_key.as_jwk().unwrap();
+                let private = t.private_key.as_jwk().unwrap();
+                assert_eq!(public.kty, "EC");
+                assert_eq!(public.d, None);
+                assert_eq!(group.curve.prime(), Some(private.curve));
+                assert!(private.d.is_some());
+                assert!(t.public_key.as_bytes().is_none());
+            }
+        }
+    }
+
+    let pem = [
+        TestName::EcdhSecp224r1Pem,
+        TestName::EcdhSecp256r1Pem,
+        TestName::EcdhSecp384r1Pem,
+        TestName::EcdhSecp521r1Pem,
+    ];
+    for test in pem {
+        let kat = wycheproof::ecdh::TestSet::load(test)?;
+        for group in kat.test_groups {
+            assert_eq!(group.encoding, EcdhEncoding::Pem);
+            for t in group.tests {
+                let public = t.public_key.as_pem().unwrap();
+                let private = t.private_key.as_pem().unwrap();
+                assert_eq!(public.label(), Ok("PUBLIC KEY"));
+                assert_eq!(private.label(), Ok("PRIVATE KEY"));
+                assert!(public.to_der().is_ok() && private.to_der().is_ok());
+                assert_eq!(t.private_key.private_scalar(), None);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "num-bigint")]
+#[test]
+fn test_ec_point_arithmetic() {
+    use wycheproof::ec_point::EcPoint;
+
+    for curve in wycheproof::EllipticCurve::all() {
+        let g = curve.generator();
+        assert!(g.is_on_curve(curve));
+        assert_eq!(g.validate(curve), Ok(()));
+
+        let n = &curve.params().n;
+        assert_eq!(g.mul(curve, n), Ok(EcPoint::Identity));
+        assert!(!curve.is_valid_scalar(n));
+        assert!(!curve.is_valid_scalar(&[0]));
+        assert!(curve.is_valid_scalar(&[1]));
+    }
+}
+
+#[cfg(all(feature = "ecdh", feature = "num-bigint"))]
+#[test]
+fn test_ecdh_public_point_validation() -> Result<(), wycheproof::WycheproofError> {
+    use wycheproof::ec_point::{PointEncoding, PointError};
+    use wycheproof::ecdh::{EcdhEncoding, TestFlag};
+
+    for test in wycheproof::ecdh::TestName::all() {
+        let kat = wycheproof::ecdh::TestSet::load(test)?;
+        for group in kat.test_groups {
+            if !matches!(
+                group.encoding,
+                EcdhEncoding::EcPoint | EcdhEncoding::Webcrypto
+            ) {
+                continue;
+            }
+            let curve = group.curve.prime().unwrap();
+            for t in &group.tests {
+                let public = t.public_point(&group);
+
+                if t.flags.contains(&TestFlag::InvalidCurveAttack) {
+                    assert!(matches!(
+                        public,
+                        Err(PointError::NotOnCurve | PointError::CoordinateOutOfRange)
+                    ));
+                }
+                if t.flags.contains(&TestFlag::InvalidCompressedPublic) {
+                    assert_eq!(public, Err(PointError::InvalidCompressedPoint));
+                }
+                if group.encoding == EcdhEncoding::Webcrypto
+                    && t.flags.contains(&TestFlag::WrongCurve)
+                {
+                    assert_eq!(public, Err(PointError::WrongCurve));
+                }
+                let private = t.private_key.private_scalar().unwrap();
+                if !t.result.must_fail() {
+                    assert!(public.is_ok());
+                    assert!(curve.is_valid_scalar(private));
+                }
+                if t.flags.contains(&TestFlag::Normal) {
+                    let shared = public.unwrap().mul(curve, private).unwrap();
+                    assert!(!shared.is_identity());
+                    let encoded = shared.to_sec1(curve, PointEncoding::Compressed).unwrap();
+                    assert_eq!(&encoded[1..], t.shared_secret.as_slice());
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(all(feature = "ecdh", feature = "num-bigint"))]
+#[test]
+fn test_ecdh_spki_public_point() -> Result<(), wycheproof::WycheproofError> {
+    use wycheproof::ec_point::PointError;
+    use wycheproof::ecdh::{EcdhEncoding, TestFlag};
+
+    for test in wycheproof::ecdh::TestName::all() {
+        let kat = wycheproof::ecdh::TestSet::load(test)?;
+        for group in kat.test_groups {
+            if !matches!(group.encoding, EcdhEncoding::Asn1 | EcdhEncoding::Pem) {
+                continue;
+            }
+            for t in &group.tests {
+                let public = t.public_point(&group);
+                if group.curve.prime().is_none() {
+                    assert_eq!(public, Err(PointError::UnsupportedCurve));
+                    continue;
+                }
+
+                if t.result == wycheproof::TestResult::Valid {
+                    assert!(public.is_ok(), "{}", t.tc_id);
+                }
+                if t.flags.contains(&TestFlag::UnnamedCurve) {
+                    assert_eq!(public, Err(PointError::UnsupportedEncoding));
+                }
+                if t.flags == [TestFlag::WrongCurve] {
+                    assert_eq!(public, Err(PointError::WrongCurve));
+                }
+                if t.flags.contains(&TestFlag::InvalidCurveAttack) {
+                    assert!(matches!(
+                        public,
+                        Err(PointError::NotOnCurve | PointError::CoordinateOutOfRange)
+                    ));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "ecdsa")]
+#[test]
+fn test_ecdsa_signature_encoding() -> Result<(), wycheproof::WycheproofError> {
+    use wycheproof::ecdsa::{TestFlag, TestGroupTypeId};
+    use wycheproof::signature::{self, Signature};
+    use wycheproof::TestResult;
+
+    for test in wycheproof::ecdsa::TestName::all() {
+        let kat = wycheproof::ecdsa::TestSet::load(test)?;
+        for group in kat.test_groups {
+            let width = group.p1363_width();
+            for t in &group.tests {
+                let sig = t.signature(&group);
+
+                if t.flags.contains(&TestFlag::BerEncodedSignature) {
+                    assert!(sig.is_err());
+                }
+                if t.result != TestResult::Valid {
+                    continue;
+                }
+
+                let sig = sig.unwrap();
+                match group.test_type {
+                    TestGroupTypeId::EcdsaP1363 => {
+                        assert_eq!(sig.to_p1363(width).unwrap(), t.sig.as_slice());
+                        let der = signature::p1363_to_der(&t.sig, width).unwrap();
+                        assert_eq!(Signature::from_der(&der).unwrap(), sig);
+                    }
+                    _ => {
+                        assert_eq!(sig.to_der(), t.sig.as_slice());
+                        let p1363 = signature::der_to_p1363(&t.sig, width).unwrap();
+                        assert_eq!(
+                            signature::p1363_to_der(&p1363, width).unwrap(),
+                            t.sig.as_slice()
+                        );
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "dsa")]
+#[test]
+fn test_dsa_signature_encoding() -> Result<(), wycheproof::WycheproofError> {
+    use wycheproof::der::DerError;
+    use wycheproof::dsa::{TestFlag, TestGroupTypeId};
+    use wycheproof::signature::SignatureError;
+    use wycheproof::TestResult;
+
+    for test in wycheproof::dsa::TestName::all() {
+        let kat = wycheproof::dsa::TestSet::load(test)?;
+        for group in kat.test_groups {
+            for t in &group.tests {
+                let sig = t.signature(&group);
+
+                if t.flags.contains(&TestFlag::BerEncodedSignature) {
+                    assert!(sig.is_err());
+                }
+                if t.flags.contains(&TestFlag::MissingZero) {
+                    assert_eq!(sig, Err(SignatureError::Der(DerError::NegativeInteger)));
+                }
+                if t.result == TestResult::Valid {
+                    let sig = sig.unwrap();
+                    let encoded = match group.test_type {
+                        TestGroupTypeId::DsaVerify => sig.to_der(),
+                        TestGroupTypeId::DsaVerifyP1363 => {
+                            sig.to_p1363(group.p1363_width()).unwrap()
+                        }
+                    };
+                    assert_eq!(encoded, t.sig.as_slice());
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_hash_function_metadata() {
+    use wycheproof::HashFunction;
+
+    for hash in HashFunction::all() {
+        let name = format!("\"{}\"", hash.name());
+        assert_eq!(serde_json::from_str::<HashFunction>(&name).unwrap(), hash);
+
+        match hash.pkcs1_digest_info_prefix() {
+            None => assert!(hash.is_xof()),
+            Some(prefix) => {
+                assert!(!hash.is_xof());
+                // SEQUENCE length covers the remainder of the prefix plus the digest
+                assert_eq!(prefix[1] as usize, prefix.len() - 2 + hash.output_len());
+                assert_eq!(prefix[prefix.len() - 1] as usize, hash.output_len());
+            }
+        }
+    }
+}
+
+#[cfg(feature = "digest")]
+#[test]
+fn test_hash_function_digest() {
+    use wycheproof::HashFunction;
+
+    let kats = [
+        (HashFunction::Sha1, "a9993e364706816aba3e25717850c26c9cd0d89d"),
+        (HashFunction::Sha2_224, "23097d223405d8228642a477bda255b32aadbce4bda0b3f7e36c9da7"),
+        (HashFunction::Sha2_256, "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"),
+        (HashFunction::Sha2_384, "cb00753f45a35e8bb5a03d699ac65007272c32ab0eded1631a8b605a43ff5bed8086072ba1e7cc2358baeca134c825a7"),
+        (HashFunction::Sha2_512, "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f"),
+        (HashFunction::Sha2_512_224, "4634270f707b6a54daae7530460842e20e37ed265ceee9a43e8924aa"),
+        (HashFunction::Sha2_512_256, "53048e2681941ef99b2e29b76b4c7dabe4c2d0c634fc6d46e0e2f13107e7af23"),
+        (HashFunction::Sha3_224, "e642824c3f8cf24ad09234ee7d3c766fc9a3a5168d0c94ad73b46fdf"),
+        (HashFunction::Sha3_256, "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532"),
+        (HashFunction::Sha3_384, "ec01498288516fc926459f58e2c6ad8df9b473cb0fc08c2596da7cf0e49be4b298d88cea927ac7f539f1edf228376d25"),
+        (HashFunction::Sha3_512, "b751850b1a57168a5693cd924b6b096e08f621827444f70d884f5d0240d2712e10e116e9192af3c91a7ec57647e3934057340b4cf408d5a56592f8274eec53f0"),
+        (HashFunction::Shake128, "5881092dd818bf5cf8a3ddb793fbcba74097d5c526a6d35f97b83351940f2cc8"),
+        (HashFunction::Shake256, "483366601360a8771c6863080cc4114d8db44530f8f1e1ee4f94ea37e78b5739d5a15bef186a5386c75744c0527e1faa9f8726e462a12a4feb06bd8801e751e4"),
+    ];
+
+    for (hash, expected) in kats {
+        let digest = hash.digest(b"abc");
+        assert_eq!(digest.len(), hash.output_len());
+        assert_eq!(data_encoding::HEXLOWER.encode(&digest), expected);
+    }
+}
+
+#[cfg(all(feature = "rsa_sig", feature = "num-bigint"))]
+#[test]
+fn test_rsa_pkcs1_signature_padding() -> Result<(), wycheproof::WycheproofError> {
+    use wycheproof::rsa_pkcs1_verify::TestFlag;
+    use wycheproof::TestResult;
+
+    for test in wycheproof::rsa_pkcs1_verify::TestName::all() {
+        let kat = wycheproof::rsa_pkcs1_verify::TestSet::load(test)?;
+        for group in kat.test_groups {
+            for t in &group.tests {
+                let decoded = t.decode_signature(&group);
+
+                match t.result {
+                    TestResult::Valid => {
+                        let decoded = decoded.unwrap();
+                        assert!(decoded.is_valid_for(group.hash));
+                        #[cfg(feature = "digest")]
+                        assert_eq!(decoded.digest_info.unwrap().digest, t.digest(&group));
+                    }
+                    TestResult::Invalid => {
+                        let structurally_valid = match &decoded {
+                            Ok(d) => d.is_valid_for(group.hash),
+                            Err(_) => false,
+                        };
+
+                        if t.flags.iter().any(|f| {
+                            matches!(
+                                f,
+                                TestFlag::InvalidPadding
+                                    | TestFlag::ShortPadding
+                                    | TestFlag::MissingNull
+                                    | TestFlag::BerEncodedPadding
+                            )
+                        }) {
+                            assert!(!structurally_valid, "{}", t.tc_id);
+                        }
+
+                        #[cfg(feature = "digest")]
+                        if structurally_valid {
+                            let digest = decoded.unwrap().digest_info.unwrap().digest;
+                            assert_ne!(digest, t.digest(&group), "{}", t.tc_id);
+                        }
+                    }
+                    TestResult::Acceptable => {}
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(all(feature = "rsa_sig", feature = "num-bigint"))]
+#[test]
+fn test_rsa_pkcs1_corrupted_padding() {
+    use wycheproof::rsa_padding::Pkcs1v15Decoding;
+    use wycheproof::HashFunction;
+
+    let hash = HashFunction::Sha2_256;
+    let mut em = vec![0x00, 0x01];
+    em.extend_from_slice(&[0xff; 16]);
+    em.push(0x00);
+    em.extend_from_slice(hash.pkcs1_digest_info_prefix().unwrap());
+    em.extend_from_slice(&[0x42; 32]);
+
+    let decoded = Pkcs1v15Decoding::decode(&em);
+    assert_eq!(decoded.padding_len, 16);
+    assert!(decoded.is_valid_for(hash));
+
+    em[7] = 0x5a;
+    let decoded = Pkcs1v15Decoding::decode(&em);
+    assert_eq!(decoded.padding_len, 16);
+    assert!(!decoded.padding_is_ff && decoded.has_separator);
+    assert!(decoded.digest_info.is_ok());
+    assert!(!decoded.is_valid_for(hash));
+}
+
+#[cfg(all(feature = "rsa_sig", feature = "num-bigint", feature = "digest"))]
+#[test]
+fn test_rsa_pss_signature_padding() -> Result<(), wycheproof::WycheproofError> {
+    use wycheproof::TestResult;
+
+    for test in wycheproof::rsa_pss_verify::TestName::all() {
+        let kat = wycheproof::rsa_pss_verify::TestSet::load(test)?;
+        for group in kat.test_groups {
+            for t in &group.tests {
+                let decoded = t.decode_signature(&group);
+
+                match t.result {
+                    TestResult::Valid => {
+                        let decoded = decoded.unwrap();
+                        assert_eq!(decoded.trailer, 0xbc);
+                        assert_eq!(decoded.salt_len, Some(group.salt_size));
+                        assert!(decoded.is_valid_for(group.salt_size));
+                    }
+                    TestResult::Invalid => {
+                        if let Ok(d) = decoded {
+                            assert!(!d.is_valid_for(group.salt_size), "{}", t.tc_id);
+                        }
+                    }
+                    TestResult::Acceptable => {}
+                }
+            }
+        }
+    }
+
+    // Encodings too short for the modulus are rejected rather than panicking
+    use wycheproof::rsa_padding::PssDecoding;
+    use wycheproof::{HashFunction, Mgf};
+    let sha256 = Some(HashFunction::Sha2_256);
+    for (em, modulus_bits) in [(&[][..], 0), (&[0xbc][..], 1), (&[0x00, 0xbc][..], 2048)] {
+        let d = PssDecoding::decode(
+            em,
+            modulus_bits,
+            b"",
+            HashFunction::Sha2_256,
+            Mgf::Mgf1,
+            sha256,
+        );
+        assert!(!d.leftmost_bits_clear);
+        assert_eq!(d.salt_len, None);
+        assert!(!d.hash_matches);
+    }
+    Ok(())
+}
+
+#[cfg(feature = "rsa_enc")]
+#[test]
+fn test_rsa_private_key_encodings() -> Result<(), wycheproof::WycheproofError> {
+    use wycheproof::RsaPrivate;
+
+    for test in wycheproof::rsa_oaep::TestName::all() {
+        let kat = wycheproof::rsa_oaep::TestSet::load(test)?;
+        for group in kat.test_groups {
+            let key = &group.key;
+            assert_eq!(key.to_pkcs8_der(), group.pkcs8.as_slice());
+            assert!(RsaPrivate::from_pkcs8_der(&group.pkcs8)
+                .unwrap()
+                .is_same_key(key));
+            assert!(RsaPrivate::from_pkcs1_der(&key.to_pkcs1_der())
+                .unwrap()
+                .is_same_key(key));
+            if let Some(jwk) = &group.jwk {
+                assert!(jwk.to_private().is_same_key(key));
+                assert_eq!(jwk.public().to_pkcs1_der(), key.public().to_pkcs1_der());
+            }
+            #[cfg(feature = "num-bigint")]
+            assert_eq!(key.check(), Ok(()));
+        }
+    }
+
+    for test in wycheproof::rsa_pkcs1_decrypt::TestName::all() {
+        let kat = wycheproof::rsa_pkcs1_decrypt::TestSet::load(test)?;
+        for group in kat.test_groups {
+            assert_eq!(group.key.to_pkcs8_der(), group.pkcs8.as_slice());
+            #[cfg(feature = "num-bigint")]
+            assert_eq!(group.key.check(), Ok(()));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "rsa_sig")]
+#[test]
+fn test_rsa_public_key_encoding() -> Result<(), wycheproof::WycheproofError> {
+    for test in wycheproof::rsa_pkcs1_verify::TestName::all() {
+        let kat = wycheproof::rsa_pkcs1_verify::TestSet::load(test)?;
+        for group in kat.test_groups {
+            assert_eq!(group.key.to_pkcs1_der(), group.asn_key.as_slice());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "rsa_enc")]
+#[test]
+fn test_rsa_three_primes() -> Result<(), wycheproof::WycheproofError> {
+    use wycheproof::rsa_oaep::TestName;
+
+    for test in [
+        TestName::RsaThreePrimes2048Sha1Mgf1Sha1,
+        TestName::RsaThreePrimes3072Sha224Mgf1Sha224,
+        TestName::RsaThreePrimes4096Sha256Mgf1Sha256,
+    ] {
+        let kat = wycheproof::rsa_oaep::TestSet::load(test)?;
+        for group in kat.test_groups {
+            assert_eq!(group.key.other_primes.len(), 1);
+            assert_eq!(group.key.to_pkcs8_der(), group.pkcs8.as_slice());
+            #[cfg(feature = "num-bigint")]
+            assert_eq!(group.key.check(), Ok(()));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "rsa_enc")]
+#[test]
+fn test_rsa_oaep_params() -> Result<(), wycheproof::WycheproofError> {
+    use wycheproof::rsa_oaep::TestName;
+    use wycheproof::{HashFunction, Mgf};
+
+    let params = TestName::Rsa3072Sha512_256Mgf1Sha512_256.params().unwrap();
+    assert_eq!(params.modulus_bits, 3072);
+    assert_eq!(params.hash, HashFunction::Sha2_512_256);
+    assert_eq!(params.mgf, Mgf::Mgf1);
+    assert_eq!(params.mgf_hash, Some(HashFunction::Sha2_512_256));
+    assert_eq!(params.salt_len, None);
+    assert_eq!(TestName::RsaMisc.params(), None);
+
+    for test in TestName::all() {
+        let kat = wycheproof::rsa_oaep::TestSet::load(test)?;
+        if let Some(params) = test.params() {
+            for group in &kat.test_groups {
+                assert_eq!(group.params(), params);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "rsa_sig")]
+#[test]
+fn test_rsa_pss_params() -> Result<(), wycheproof::WycheproofError> {
+    use wycheproof::rsa_pss_verify::TestName;
+    use wycheproof::{HashFunction, Mgf};
+
+    let params = TestName::RsaPss2048Sha256Mgf1SaltLen32WithParams
+        .params()
+        .unwrap();
+    assert_eq!(params.modulus_bits, 2048);
+    assert_eq!(params.hash, HashFunction::Sha2_256);
+    assert_eq!(params.mgf_hash, Some(HashFunction::Sha2_256));
+    assert_eq!(params.salt_len, Some(32));
+    assert!(params.with_params);
+
+    let params = TestName::RsaPss3072Shake256.params().unwrap();
+    assert_eq!(params.mgf, Mgf::Shake256);
+    assert_eq!(params.mgf_hash, None);
+    assert_eq!(params.salt_len, Some(64));
+    assert!(!params.with_params);
+
+    assert_eq!(TestName::RsaPssmiscWithParams.params(), None);
+
+    for test in TestName::all() {
+        let kat = wycheproof::rsa_pss_verify::TestSet::load(test)?;
+        if let Some(params) = test.params() {
+            for group in &kat.test_groups {
+                assert_eq!(group.params(), params);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "rsa_sig")]
+#[test]
+fn test_rsa_pss_key_params() -> Result<(), wycheproof::WycheproofError> {
+    use wycheproof::rsa_pss_verify::{PssParams, PssParamsError, TestGroupTypeId};
+
+    for test in wycheproof::rsa_pss_verify::TestName::all() {
+        let kat = wycheproof::rsa_pss_verify::TestSet::load(test)?;
+        for group in kat.test_groups {
+            let params = group.pss_params().unwrap();
+            if group.test_type == TestGroupTypeId::RsaPssVerifyWithParam {
+                assert!(params.unwrap().matches(&group));
+            } else {
+                assert_eq!(params, None);
+            }
+        }
+    }
+
+    let hex = |s: &str| data_encoding::HEXLOWER.decode(s.as_bytes()).unwrap();
+
+    assert_eq!(PssParams::from_der(&hex("3000")), Ok(PssParams::default()));
+    for (der, err) in [
+        (
+            "300da00b300906052b0e03021a0500",
+            PssParamsError::DefaultValueEncoded,
+        ),
+        ("3005a203020114", PssParamsError::DefaultValueEncoded),
+        ("3005a303020101", PssParamsError::DefaultValueEncoded),
+        ("3005a303020102", PssParamsError::InvalidTrailer),
+        ("3009a107300506032a0304", PssParamsError::UnknownMgf),
+        ("3009a007300506032a0304", PssParamsError::UnknownHash),
+        (
+            "300aa203020120a203020120",
+            PssParamsError::Der(wycheproof::der::DerError::TrailingData),
+        ),
+    ] {
+        assert_eq!(PssParams::from_der(&hex(der)), Err(err), "{}", der);
+    }
+    Ok(())
+}
+
+#[cfg(feature = "ecdsa")]
+#[test]
+fn test_ecdsa_spki() -> Result<(), wycheproof::WycheproofError> {
+    use wycheproof::der::{SubjectPublicKeyInfo, EC_PUBLIC_KEY_OID};
+
+    for test in wycheproof::ecdsa::TestName::all() {
+        let kat = wycheproof::ecdsa::TestSet::load(test)?;
+        for group in kat.test_groups {
+            let spki = SubjectPublicKeyInfo::from_der(&group.der).unwrap();
+            assert_eq!(spki.algorithm.oid, EC_PUBLIC_KEY_OID);
+            assert_eq!(spki.ec_curve(), Some(group.key.curve));
+            assert!(group.key.matches_spki(&spki));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(any(feature = "rsa_enc", feature = "rsa_sig"))]
+#[test]
+fn test_rsa_spki_and_pkcs8() -> Result<(), wycheproof::WycheproofError> {
+    #[allow(unused_imports)]
+    use wycheproof::der::{PrivateKeyInfo, SubjectPublicKeyInfo};
+
+    #[cfg(feature = "rsa_enc")]
+    for test in wycheproof::rsa_oaep::TestName::all() {
+        let kat = wycheproof::rsa_oaep::TestSet::load(test)?;
+        for group in kat.test_groups {
+            let info = PrivateKeyInfo::from_der(&group.pkcs8).unwrap();
+            assert_eq!(info.version, 0);
+            assert_eq!(info.public_key, None);
+            assert!(group.key.matches_pkcs8(&info));
+            assert!(group.key.public().matches_spki(&SubjectPublicKeyInfo {
+                algorithm: info.algorithm.clone(),
+                public_key: &group.key.public().to_pkcs1_der(),
+            }));
+        }
+    }
+
+    #[cfg(feature = "rsa_sig")]
+    for test in wycheproof::rsa_pkcs1_verify::TestName::all() {
+        let kat = wycheproof::rsa_pkcs1_verify::TestSet::load(test)?;
+        for group in kat.test_groups {
+            let spki = SubjectPublicKeyInfo::from_der(&group.der).unwrap();
+            assert_eq!(spki.ec_curve(), None);
+            assert!(group.key.matches_spki(&spki));
+        }
+    }
+
+    #[cfg(feature = "rsa_sig")]
+    for test in wycheproof::rsa_pss_verify::TestName::all() {
+        let kat = wycheproof::rsa_pss_verify::TestSet::load(test)?;
+        for group in kat.test_groups {
+            let spki = SubjectPublicKeyInfo::from_der(&group.der).unwrap();
+            assert!(group.key.matches_spki(&spki));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(any(feature = "mldsa_sign", feature = "mldsa_verify"))]
+#[test]
+fn test_mldsa_spki_and_pkcs8() -> Result<(), wycheproof::WycheproofError> {
+    #[allow(unused_imports)]
+    use wycheproof::der::{PrivateKeyInfo, SubjectPublicKeyInfo};
+
+    #[cfg(feature = "mldsa_verify")]
+    for test in wycheproof::mldsa_verify::TestName::all() {
+        let kat = wycheproof::mldsa_verify::TestSet::load(test)?;
+        for group in kat.test_groups {
+            let spki = match SubjectPublicKeyInfo::from_der(&group.der) {
+                Ok(spki) => spki,
+                Err(_) => {
+                    // Only keys of the wrong length have an invalid encoding
+                    assert!(group.tests.iter().all(|t| t
+                        .flags
+                        .contains(&wycheproof::mldsa_verify::TestFlag::IncorrectPublicKeyLength)));
+                    continue;
+                }
+            };
+            assert_eq!(spki.algorithm.oid, kat.algorithm.oid());
+            assert_eq!(spki.algorithm.parameters, None);
+            assert_eq!(spki.public_key, group.pubkey.as_slice());
+        }
+    }
+
+    #[cfg(feature = "mldsa_sign")]
+    for test in wycheproof::mldsa_sign::TestName::all() {
+        let kat = wycheproof::mldsa_sign::TestSet::load(test)?;
+        for group in kat.test_groups {
+            let (pkcs8, seed) = match (&group.privkey_pkcs8, &group.privseed) {
+                // Seeds of an invalid length have no PKCS #8 encoding
+                (Some(pkcs8), Some(seed)) if !pkcs8.is_empty() => (pkcs8, seed),
+                _ => continue,
+            };
+            let info = PrivateKeyInfo::from_der(pkcs8).unwrap();
+            assert_eq!(info.algorithm.oid, kat.algorithm.oid());
+            assert_eq!(info.algorithm.parameters, None);
+            // The seed form of the ML-DSA private key CHOICE is [0] IMPLICIT
+            assert_eq!(info.private_key[..2], [0x80, seed.len() as u8]);
+            assert_eq!(&info.private_key[2..], seed.as_slice());
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_pem_decoding() {
+    use wycheproof::{Pem, PemError};
+
+    let pem = |s: &str| serde_json::from_value::<Pem>(serde_json::Value::from(s)).unwrap();
+
+    let p = pem("-----BEGIN TEST-----\nAAEC\nAw==\n-----END TEST-----\n");
+    assert_eq!(p.label(), Ok("TEST"));
+    assert_eq!(p.to_der(), Ok(vec![0, 1, 2, 3]));
+    assert_eq!(p.check("TEST", &[0, 1, 2, 3]), Ok(()));
+    assert_eq!(p.check("TEST", &[0, 1, 2]), Err(PemError::DerMismatch));
+    assert_eq!(
+        p.check("OTHER", &[0, 1, 2, 3]),
+        Err(PemError::UnexpectedLabel)
+    );
+
+    for (s, err) in [
+        ("AAEC\n-----END TEST-----", PemError::InvalidHeader),
+        ("-----BEGIN TEST-----\nAAEC", PemError::InvalidFooter),
+        (
+            "-----BEGIN TEST-----\nAAEC\n-----END OTHER-----",
+            PemError::InvalidFooter,
+        ),
+        (
+            "-----BEGIN TEST-----\nAAE\n-----END TEST-----",
+            PemError::InvalidBase64,
+        ),
+    ] {
+        assert_eq!(pem(s).to_der(), Err(err), "{}", s);
+    }
+}
+
+#[cfg(any(feature = "dsa", feature = "ecdsa", feature = "eddsa"))]
+#[test]
+fn test_public_key_pem() -> Result<(), wycheproof::WycheproofError> {
+    #[cfg(feature = "dsa")]
+    for test in wycheproof::dsa::TestName::all() {
+        let kat = wycheproof::dsa::TestSet::load(test)?;
+        for group in kat.test_groups {
+            assert_eq!(group.pem.check("PUBLIC KEY", &group.der), Ok(()));
+        }
+    }
+
+    #[cfg(feature = "ecdsa")]
+    for test in wycheproof::ecdsa::TestName::all() {
+        let kat = wycheproof::ecdsa::TestSet::load(test)?;
+        for group in kat.test_groups {
+            assert_eq!(group.pem.check("PUBLIC KEY", &group.der), Ok(()));
+        }
+    }
+
+    #[cfg(feature = "eddsa")]
+    for test in wycheproof::eddsa::TestName::all() {
+        let kat = wycheproof::eddsa::TestSet::load(test)?;
+        for group in kat.test_groups {
+            assert_eq!(group.pem.check("PUBLIC KEY", &group.der), Ok(()));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(any(feature = "rsa_enc", feature = "rsa_sig"))]
+#[test]
+fn test_rsa_key_pem() -> Result<(), wycheproof::WycheproofError> {
+    #[cfg(feature = "rsa_enc")]
+    for test in wycheproof::rsa_oaep::TestName::all() {
+        let kat = wycheproof::rsa_oaep::TestSet::load(test)?;
+        for group in kat.test_groups {
+            assert_eq!(group.pem.check("PRIVATE KEY", &group.pkcs8), Ok(()));
+        }
+    }
+
+    #[cfg(feature = "rsa_enc")]
+    for test in wycheproof::rsa_pkcs1_decrypt::TestName::all() {
+        let kat = wycheproof::rsa_pkcs1_decrypt::TestSet::load(test)?;
+        for group in kat.test_groups {
+            assert_eq!(group.pem.check("PRIVATE KEY", &group.pkcs8), Ok(()));
+        }
+    }
+
+    #[cfg(feature = "rsa_sig")]
+    for test in wycheproof::rsa_pkcs1_sign::TestName::all() {
+        let kat = wycheproof::rsa_pkcs1_sign::TestSet::load(test)?;
+        for group in kat.test_groups {
+            assert_eq!(group.pem.check("PUBLIC KEY", &group.der), Ok(()));
+            // The private key PEM is the PKCS #1 key wrapped by the PKCS #8 key
+            assert_eq!(group.private_pem.label(), Ok("RSA PRIVATE KEY"));
+            assert!(group.pkcs8.ends_with(&group.private_pem.to_der().unwrap()));
+        }
+    }
+
+    #[cfg(feature = "rsa_sig")]
+    for test in wycheproof::rsa_pkcs1_verify::TestName::all() {
+        let kat = wycheproof::rsa_pkcs1_verify::TestSet::load(test)?;
+        for group in kat.test_groups {
+            assert_eq!(group.pem.check("PUBLIC KEY", &group.der), Ok(()));
+        }
+    }
+
+    #[cfg(feature = "rsa_sig")]
+    for test in wycheproof::rsa_pss_verify::TestName::all() {
+        let kat = wycheproof::rsa_pss_verify::TestSet::load(test)?;
+        for group in kat.test_groups {
+            assert_eq!(group.pem.check("PUBLIC KEY", &group.der), Ok(()));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "rsa_sig")]
+#[test]
+fn test_rsa_pkcs1_sign_keys() -> Result<(), wycheproof::WycheproofError> {
+    for test in wycheproof::rsa_pkcs1_sign::TestName::all() {
+        let kat = wycheproof::rsa_pkcs1_sign::TestSet::load(test)?;
+        for group in kat.test_groups {
+            let public = group.key.public();
+            assert_eq!(public.to_pkcs1_der(), group.asn_key.as_slice());
+            if let Some(jwk) = &group.private_jwk {
+                assert_eq!(jwk.public().to_pkcs1_der(), group.asn_key.as_slice());
+            }
+
+            #[cfg(feature = "rsa_enc")]
+            {
+                let full = wycheproof::RsaPrivate::from_pkcs8_der(&group.pkcs8).unwrap();
+                assert_eq!(full.to_pkcs8_der(), group.pkcs8.as_slice());
+                assert_eq!(full.public().to_pkcs1_der(), group.asn_key.as_slice());
+                let trim = |v: &[u8]| {
+                    v.iter()
+                        .copied()
+                        .skip_while(|&b| b == 0)
+                        .collect::<Vec<u8>>()
+                };
+                assert_eq!(trim(&full.d), trim(&group.key.d));
+            }
+
+            #[cfg(feature = "num-bigint")]
+            for t in &group.tests {
+                let decoded = wycheproof::rsa_padding::Pkcs1v15Decoding::decode(
+                    &public.recover(&t.sig).unwrap(),
+                );
+                assert!(decoded.is_valid_for(group.hash));
+                #[cfg(feature = "digest")]
+                assert_eq!(
+                    decoded.digest_info.unwrap().digest,
+                    group.hash.digest(&t.msg)
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "bls")]
+#[test]
+fn test_bls_parsing() -> Result<(), wycheproof::WycheproofError> {
+    use wycheproof::bls::{aggregate_verify, hash_to_g2, verify};
+
+    for test in verify::TestName::all() {
+        let kat = verify::TestSet::load(test)?;
+        for group in kat.test_groups {
+            assert_eq!(group.key.group, wycheproof::BlsGroup::G1);
+            assert_eq!(group.key.key_size, 48);
+            for t in &group.tests {
+                if t.result == wycheproof::TestResult::Valid {
+                    assert_eq!(group.key.pk.len(), 48);
+                    assert_eq!(t.sig.len(), 96);
+                }
+            }
+        }
+    }
+
+    for test in aggregate_verify::TestName::all() {
+        let kat = aggregate_verify::TestSet::load(test)?;
+        for group in kat.test_groups {
+            assert_eq!(group.ciphersuite, wycheproof::bls::BlsCiphersuite::G2Basic);
+            for t in &group.tests {
+                if t.result == wycheproof::TestResult::Valid {
+                    assert_eq!(t.pubkeys.len(), t.messages.len());
+                    assert!(!t.pubkeys.is_empty());
+                }
+            }
+        }
+    }
+
+    for test in hash_to_g2::TestName::all() {
+        let kat = hash_to_g2::TestSet::load(test)?;
+        for group in kat.test_groups {
+            for t in &group.tests {
+                assert_eq!(t.point.len(), 96);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "bls")]
+#[test]
+fn test_bls_ciphersuite() -> Result<(), wycheproof::WycheproofError> {
+    use wycheproof::bls::{verify, BlsCiphersuite};
+
+    let kat = verify::TestSet::load(verify::TestName::G2ProofOfPossession)?;
+    for group in kat.test_groups {
+        assert_eq!(group.ciphersuite, BlsCiphersuite::G2ProofOfPossession);
+        assert_eq!(group.ciphersuite.dst(), group.ciphersuite.name().as_bytes());
+        assert!(group.ciphersuite.name().ends_with("_POP_"));
+    }
+    Ok(())
+}
+
+#[cfg(feature = "jose")]
+#[test]
+fn test_jose_parsing() -> Result<(), wycheproof::WycheproofError> {
+    use wycheproof::jose::{crypto, encryption, key, signature, JoseObject};
+
+    for test in signature::TestName::all() {
+        let kat = signature::TestSet::load(test)?;
+        for group in kat.test_groups {
+            for t in &group.tests {
+                if t.result == wycheproof::TestResult::Valid {
+                    if let JoseObject::Compact(_) = t.jws {
+                        assert_eq!(t.jws.compact_parts().unwrap().len(), 3);
+                    }
+                }
+            }
+        }
+    }
+
+    for test in encryption::TestName::all() {
+        let kat = encryption::TestSet::load(test)?;
+        for group in kat.test_groups {
+            for t in &group.tests {
+                if t.result == wycheproof::TestResult::Valid {
+                    assert!(t.pt.is_some());
+                }
+            }
+        }
+    }
+
+    for test in key::TestName::all() {
+        let _kat = key::TestSet::load(test)?;
+    }
+
+    for test in crypto::TestName::all() {
+        let kat = crypto::TestSet::load(test)?;
+        for group in kat.test_groups {
+            for t in &group.tests {
+                assert!(t.jws.is_some() != t.jwe.is_some());
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "jose")]
+#[test]
+fn test_jose_keys() -> Result<(), wycheproof::WycheproofError> {
+    use wycheproof::jose::key::{TestFlag, TestName, TestSet};
+    use wycheproof::{JwkKeyType, JwkOrSet};
+
+    let kat = TestSet::load(TestName::JsonWebKey)?;
+    for group in kat.test_groups {
+        for t in &group.tests {
+            if t.flags.contains(&TestFlag::MixedKeySet) {
+                let keys = group.private.keys();
+                assert!(matches!(group.private, JwkOrSet::Set(_)));
+                assert!(keys.iter().any(|k| k.kty != keys[0].kty));
+            }
+            if t.flags.contains(&TestFlag::DuplicateKid) {
+                let keys = group.private.keys();
+                assert!(keys.iter().all(|k| k.kid == keys[0].kid));
+            }
+        }
+
+        for key in group.private.keys() {
+            if key.kty == JwkKeyType::Oct {
+                assert!(key.k.is_some());
+            }
+        }
+        if let Some(public) = &group.public {
+            assert!(public.keys().iter().all(|k| !k.is_private()));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(all(feature = "jose", feature = "rsa_sig"))]
+#[test]
+fn test_jose_jwk_from_rsa() -> Result<(), wycheproof::WycheproofError> {
+    use wycheproof::rsa_pkcs1_verify::{TestName, TestSet};
+
+    let kat = TestSet::load(TestName::Rsa2048Sha256)?;
+    for group in kat.test_groups {
+        if let Some(jwk) = &group.jwk {
+            let generic = wycheproof::Jwk::from(jwk);
+            assert_eq!(generic.kty, wycheproof::JwkKeyType::Rsa);
+            assert_eq!(generic.n.as_ref(), Some(&jwk.n));
+            assert!(!generic.is_private());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(any(
+    feature = "ecdsa",
+    feature = "eddsa",
+    feature = "rsa_enc",
+    feature = "rsa_sig"
+))]
+#[test]
+fn test_jwk_matches_key() -> Result<(), wycheproof::WycheproofError> {
+    #[cfg(feature = "ecdsa")]
+    for test in wycheproof::ecdsa::TestName::all() {
+        let kat = wycheproof::ecdsa::TestSet::load(test)?;
+        for group in kat.test_groups {
+            if let Some(jwk) = &group.jwk {
+                assert_eq!(jwk.to_typed(), group.key);
+                assert_eq!(jwk.check(&group.key), Ok(()));
+            }
+        }
+    }
+
+    #[cfg(feature = "eddsa")]
+    for test in wycheproof::eddsa::TestName::all() {
+        let kat = wycheproof::eddsa::TestSet::load(test)?;
+        for group in kat.test_groups {
+            assert_eq!(group.jwk.to_typed(), group.key);
+            assert_eq!(group.jwk.check(&group.key), Ok(()));
+        }
+    }
+
+    #[cfg(feature = "rsa_sig")]
+    for test in wycheproof::rsa_pkcs1_verify::TestName::all() {
+        let kat = wycheproof::rsa_pkcs1_verify::TestSet::load(test)?;
+        for group in kat.test_groups {
+            if let Some(jwk) = &group.jwk {
+                let alg = group.jwk_alg().unwrap();
+                assert_eq!(jwk.check(&group.key, alg), Ok(()));
+                assert_eq!(
+                    jwk.check(&group.key, "none"),
+                    Err(wycheproof::JwkError::WrongAlgorithm)
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "rsa_sig")]
+    for test in wycheproof::rsa_pkcs1_sign::TestName::all() {
+        let kat = wycheproof::rsa_pkcs1_sign::TestSet::load(test)?;
+        for group in kat.test_groups {
+            let public = group.key.public();
+            if let Some(jwk) = &group.jwk {
+                assert_eq!(jwk.check(&public, group.jwk_alg().unwrap()), Ok(()));
+            }
+            if let Some(jwk) = &group.private_jwk {
+                assert_eq!(jwk.check(&public, group.jwk_alg().unwrap()), Ok(()));
+            }
+            if let (Some(jwk), Some(private_jwk)) = (&group.jwk, &group.private_jwk) {
+                assert_eq!(jwk.thumbprint_input(), private_jwk.thumbprint_input());
+            }
+        }
+    }
+
+    #[cfg(feature = "rsa_sig")]
+    for test in wycheproof::rsa_pss_verify::TestName::all() {
+        let kat = wycheproof::rsa_pss_verify::TestSet::load(test)?;
+        for group in kat.test_groups {
+            if let Some(jwk) = &group.jwk {
+                assert_eq!(jwk.check(&group.key, group.jwk_alg().unwrap()), Ok(()));
+            }
+        }
+    }
+
+    #[cfg(feature = "rsa_enc")]
+    for test in wycheproof::rsa_oaep::TestName::all() {
+        let kat = wycheproof::rsa_oaep::TestSet::load(test)?;
+        for group in kat.test_groups {
+            if let Some(jwk) = &group.jwk {
+                let public = group.key.public();
+                assert_eq!(jwk.check(&public, group.jwk_alg().unwrap()), Ok(()));
+                assert_eq!(jwk.to_private(), group.key);
+            }
+        }
+    }
+
+    #[cfg(feature = "rsa_enc")]
+    for test in wycheproof::rsa_pkcs1_decrypt::TestName::all() {
+        let kat = wycheproof::rsa_pkcs1_decrypt::TestSet::load(test)?;
+        for group in kat.test_groups {
+            if let Some(jwk) = &group.jwk {
+                let public = group.key.public();
+                assert_eq!(
+                    jwk.check(&public, wycheproof::rsa_pkcs1_decrypt::TestGroup::JWK_ALG),
+                    Ok(())
+                );
+                assert_eq!(jwk.to_private(), group.key);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(all(feature = "rsa_sig", feature = "digest"))]
+#[test]
+fn test_jwk_thumbprint() {
+    // The example from RFC 7638 section 3.1
+    let jwk: wycheproof::RsaPublicJwk = serde_json::from_str(
+        r#"{
+            "kty": "RSA",
+            "n": "0vx7agoebGcQSuuPiLJXZptN9nndrQmbXEps2aiAFbWhM78LhWx4cbbfAAtVT86zwu1RK7aPFFxuhDR1L6tSoc_BJECPebWKRXjBZCiFV4n3oknjhMstn64tZ_2W-5JsGY4Hc5n9yBXArwl93lqt7_RN5w6Cf0h4QyQ5v-65YGjQR0_FDW2QvzqY368QQMicAtaSqzs8KJZgnYb9c7d0zgdAZHzu6qMQvRL5hajrn1n91CbOpbISD08qNLyrdkt-bFTWhAI4vMQFh6WeZu0fM4lFd2NcRwr3XPksINHaQ-G_xBniIqbw0Ls1jF44-csFCur-kEgU8awapJzKnqDKgw",
+            "e": "AQAB",
+            "alg": "RS256",
+            "kid": "2011-04-29"
+        }"#,
+    )
+    .unwrap();
+
+    assert!(jwk
+        .thumbprint_input()
+        .starts_with(r#"{"e":"AQAB","kty":"RSA","n":"0vx7"#));
+    let thumbprint = jwk.thumbprint(wycheproof::HashFunction::Sha2_256);
+    assert_eq!(
+        data_encoding::BASE64URL_NOPAD.encode(&thumbprint),
+        "NzbLsXh8uDCcd-6MNwXF4W_7noWXFZAfHkxZsRGC9Xs"
+    );
+}
+
+#[test]
+fn test_large_integer_conversions() {
+    let int = |s: &str| -> wycheproof::LargeInteger {
+        serde_json::from_str(&format!("\"{}\"", s)).unwrap()
+    };
+
+    assert_eq!(int("").bits(), 0);
+    assert_eq!(int("00").bits(), 0);
+    assert_eq!(int("01").bits(), 1);
+    assert_eq!(int("00ff").bits(), 8);
+    assert_eq!(int("0100").bits(), 9);
+
+    assert_eq!(int("00ff").to_be_bytes_padded(1), Some(vec![0xff]));
+    assert_eq!(int("00ff").to_be_bytes_padded(3), Some(vec![0, 0, 0xff]));
+    assert_eq!(int("0100").to_be_bytes_padded(1), None);
+    assert_eq!(int("").to_be_bytes_padded(2), Some(vec![0, 0]));
+
+    assert_eq!(int("010001").to_u64(), Some(65537));
+    assert_eq!(int("00ffffffffffffffff").to_u64(), Some(u64::MAX));
+    assert_eq!(int("010000000000000000").to_u64(), None);
+    assert_eq!(int("010000000000000000").to_u128(), Some(1 << 64));
+    assert_eq!(int("0100000000000000000000000000000000").to_u128(), None);
+}
+
+#[cfg(feature = "rsa_enc")]
+#[test]
+fn test_rsa_large_integer_width() -> Result<(), wycheproof::WycheproofError> {
+    use wycheproof::rsa_oaep::*;
+
+    for test in TestName::all() {
+        let kat = TestSet::load(test)?;
+        for group in kat.test_groups {
+            let n = &group.key.n;
+            let len = (group.key_size + 7) / 8;
+            assert_eq!(n.bits(), group.key_size);
+            let padded = n.to_be_bytes_padded(len).unwrap();
+            assert_eq!(padded.len(), len);
+            assert!(n.ends_with(&padded));
+            assert_eq!(n.to_be_bytes_padded(len - 1), None);
+            assert!(group.key.e.to_u64().is_some());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(all(feature = "rsa_enc", feature = "crypto-bigint"))]
+#[test]
+fn test_crypto_bigint_conversion() -> Result<(), wycheproof::WycheproofError> {
+    use crypto_bigint::{Encoding, U2048, U256};
+    use wycheproof::rsa_oaep::*;
+
+    let kat = TestSet::load(TestName::Rsa2048Sha256Mgf1Sha256)?;
+    for group in kat.test_groups {
+        let n: U2048 = group.key.n.as_crypto_bigint().unwrap();
+        assert_eq!(
+            n.to_be_bytes().to_vec(),
+            group.key.n.to_be_bytes_padded(256).unwrap()
+        );
+        assert_eq!(group.key.n.as_crypto_bigint::<{ U256::LIMBS }>(), None);
+        let e: U256 = group.key.e.as_crypto_bigint().unwrap();
+        assert_eq!(e, U256::from_u64(group.key.e.to_u64().unwrap()));
+    }
+    Ok(())
+}